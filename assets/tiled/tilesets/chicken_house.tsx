@@ -0,0 +1,9 @@
+<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" tiledversion="1.11.2" name="chicken_house" tilewidth="48" tileheight="48" tilecount="1" columns="1">
+ <image source="../../game/objects/chicken_house.png" width="48" height="48"/>
+ <tile id="0">
+  <objectgroup draworder="index" id="2">
+   <object id="1" x="6" y="26" width="36" height="16"/>
+  </objectgroup>
+ </tile>
+</tileset>
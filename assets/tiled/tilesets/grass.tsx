@@ -1,5 +1,8 @@
 <?xml version="1.0" encoding="UTF-8"?>
 <tileset version="1.10" tiledversion="1.11.2" name="grass" tilewidth="16" tileheight="16" tilecount="77" columns="11">
+ <properties>
+  <property name="surface" value="grass"/>
+ </properties>
  <image source="../../game/tilesets/grass.png" width="176" height="112"/>
  <tile id="0">
   <objectgroup draworder="index" id="2">
@@ -31,6 +31,11 @@
    <object id="1" x="11" y="0" width="5" height="16"/>
   </objectgroup>
  </tile>
+ <tile id="6">
+  <properties>
+   <property name="surface" value="wood_floor"/>
+  </properties>
+ </tile>
  <tile id="7">
   <objectgroup draworder="index" id="2">
    <object id="1" x="0" y="0" width="5" height="16"/>
@@ -0,0 +1,4 @@
+<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" tiledversion="1.11.2" name="house_roof" tilewidth="16" tileheight="16" tilecount="35" columns="7">
+ <image source="../../game/tilesets/wooden_house_roof_tilset.png" width="112" height="80"/>
+</tileset>
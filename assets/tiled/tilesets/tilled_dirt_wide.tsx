@@ -1,5 +1,8 @@
 <?xml version="1.0" encoding="UTF-8"?>
 <tileset version="1.10" tiledversion="1.11.2" name="tilled_dirt_wide" tilewidth="16" tileheight="16" tilecount="77" columns="11">
+ <properties>
+  <property name="surface" value="tilled_soil"/>
+ </properties>
  <image source="../../game/tilesets/tilled_dirt_wide.png" width="176" height="112"/>
  <wangsets>
   <wangset name="TiledDirt" type="corner" tile="-1">
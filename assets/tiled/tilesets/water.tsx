@@ -1,5 +1,8 @@
 <?xml version="1.0" encoding="UTF-8"?>
 <tileset version="1.10" tiledversion="1.11.2" name="water" tilewidth="16" tileheight="16" tilecount="4" columns="4">
+ <properties>
+  <property name="surface" value="shallow_water"/>
+ </properties>
  <image source="../../game/tilesets/water.png" width="64" height="16"/>
  <tile id="0">
   <animation>
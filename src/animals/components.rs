@@ -1,190 +1,485 @@
-use super::resources::{ChickenAtlas, CowAtlas};
+use super::{
+    resources::{EggImage, NestAtlas, ProduceAtlas},
+    species::{AnimalClips, AnimalSpecies, SpriteClip},
+};
 use crate::{
     core::components::{CharacterAnimation, Speed, YSort},
-    npcs::components::{IdleTimer, Npc, WalkCycles, WanderConfig},
-    physics::GameLayer,
+    interactable::Interactable,
+    inventory::ItemKind,
+    npcs::components::{IdleTimer, Npc, WalkCycles},
+    objects::components::Item,
+    tiled::TileOccupant,
 };
 use avian2d::prelude::*;
 use bevy::{
     ecs::{lifecycle::HookContext, world::DeferredWorld},
     prelude::*,
-    sprite::Anchor,
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Chicken
+// Animal
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// A farm animal. Everything specific to its species (sprite sheet, animations,
+/// collider, wandering, produce...) comes from its species file, so adding a
+/// species needs no code. Place it on Tiled tile objects with the species id
+/// (e.g. "chicken" for `assets/game/animals/chicken.species.ron`).
 #[derive(Component, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Default)]
 #[require(
-    Name = "Chicken",
     Npc,
     Sprite,
-    ChickenAnimation,
-    WanderConfig,
+    AnimalAnimation,
     WalkCycles,
     IdleTimer,
     RigidBody::Dynamic,
     LockedAxes::ROTATION_LOCKED,
     Speed,  // Set dynamically per walk cycle from WanderConfig
-    YSort = YSort { offset: -4.0 },
+    YSort,  // Offset set from the species
+    Hunger,
+    Happiness,
+    EmoteOffset,
+    Interactable,
 )]
 #[component(on_add = Self::on_add)]
-pub struct Chicken;
-
-impl Chicken {
-    const COLLIDER_OFFSET_Y: f32 = -2.0;
+pub struct Animal {
+    /// Species id, the name of its species file.
+    pub species: String,
+}
 
+impl Animal {
     fn on_add(mut world: DeferredWorld, ctx: HookContext) {
         let entity = ctx.entity;
+        let Some(id) = world
+            .get::<Animal>(entity)
+            .map(|animal| animal.species.clone())
+        else {
+            return;
+        };
 
-        // Get atlas resource
-        let atlas = world
-            .get_resource::<ChickenAtlas>()
-            .expect("ChickenAtlas must be present");
+        // Set up once loaded, see `apply_species`
+        let species = world
+            .resource::<AssetServer>()
+            .load(AnimalSpecies::path(&id));
+        world
+            .commands()
+            .entity(entity)
+            .insert(SpeciesLoading(species));
+    }
+}
 
-        let texture = atlas.texture.clone();
-        let layout = atlas.layout.clone();
+/// Species of an animal that hasn't been set up yet.
+#[derive(Component)]
+pub struct SpeciesLoading(pub Handle<AnimalSpecies>);
 
-        // Configure sprite
-        if let Some(mut sprite) = world.get_mut::<Sprite>(entity) {
-            sprite.image = texture;
-            sprite.texture_atlas = Some(TextureAtlas { layout, index: 0 });
-        }
+/// Animation state of an animal, with the clips of its species.
+#[derive(Component, Reflect, Default, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct AnimalAnimation {
+    pub state: AnimalAnimationState,
+    pub clips: AnimalClips,
+}
 
-        // Set anchor at bottom-left to match Tiled tile object placement
-        world.commands().entity(entity).insert(Anchor::BOTTOM_LEFT);
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnimalAnimationState {
+    #[default]
+    Idle,
+    Walk,
+    Run,
+}
 
-        // Spawn collider as child (at chicken's feet)
-        // No NavMeshObstacle - chickens use real-time separation instead
-        world.commands().entity(entity).with_child((
-            Collider::circle(3.0),
-            Transform::from_translation(Vec3::new(0.0, Self::COLLIDER_OFFSET_Y, 0.0)),
-            CollisionLayers::new(
-                GameLayer::Npc,
-                [GameLayer::Default, GameLayer::Player, GameLayer::Npc],
-            ),
-        ));
+impl AnimalAnimation {
+    fn clip(self) -> SpriteClip {
+        match self.state {
+            AnimalAnimationState::Idle => self.clips.idle,
+            AnimalAnimationState::Walk => self.clips.walk,
+            AnimalAnimationState::Run => self.clips.run,
+        }
     }
-}
 
-/// Animation states for chickens.
-#[derive(Component, Reflect, Default, Clone, Copy, PartialEq, Eq)]
-#[reflect(Component)]
-pub enum ChickenAnimation {
-    #[default]
-    Idle, // frames 0-1
-    Walk, // frames 4-7
+    fn with_state(self, state: AnimalAnimationState) -> Self {
+        Self { state, ..self }
+    }
 }
 
-impl CharacterAnimation for ChickenAnimation {
+impl CharacterAnimation for AnimalAnimation {
     fn frames(self) -> (usize, usize) {
-        match self {
-            Self::Idle => (0, 1),
-            Self::Walk => (4, 7),
-        }
+        let clip = self.clip();
+        (clip.first, clip.last)
     }
 
     fn fps(self) -> u8 {
-        5
+        self.clip().fps
     }
 
     fn to_idle(self) -> Self {
-        Self::Idle
+        self.with_state(AnimalAnimationState::Idle)
     }
 
     fn to_walk(self) -> Self {
-        Self::Walk
+        self.with_state(AnimalAnimationState::Walk)
+    }
+
+    fn to_run(self) -> Self {
+        self.with_state(AnimalAnimationState::Run)
     }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Cow
+// Needs
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// How hungry an animal is, from 0 (full) to `Hunger::MAX` (starving).
+/// Rises over in-game time, lowered by feeding and grazing.
+/// Hungry animals don't produce.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct Hunger {
+    pub value: f32,
+    /// Increase per in-game hour.
+    pub per_hour: f32,
+}
+
+impl Default for Hunger {
+    fn default() -> Self {
+        Self {
+            value: 20.0,
+            per_hour: 4.0,
+        }
+    }
+}
+
+impl Hunger {
+    pub const MAX: f32 = 100.0;
+    /// At or above this the animal is hungry.
+    pub const HUNGRY: f32 = 50.0;
+    /// Grazing animals eat until below this.
+    pub const SATED: f32 = 25.0;
+
+    pub fn is_hungry(&self) -> bool {
+        self.value >= Self::HUNGRY
+    }
+
+    pub fn add(&mut self, amount: f32) {
+        self.value = (self.value + amount).clamp(0.0, Self::MAX);
+    }
+}
+
+/// How happy an animal is, from 0 to `Happiness::MAX`.
+/// Drops over in-game time (faster when hungry), raised by petting and feeding.
+/// Happy animals produce more and better, unhappy ones sulk and produce nothing.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct Happiness {
+    pub value: f32,
+    /// Decrease per in-game hour (doubled while hungry).
+    pub per_hour: f32,
+    /// Day the animal was last petted (petting counts once per day).
+    pub last_petted_day: Option<u32>,
+}
+
+impl Default for Happiness {
+    fn default() -> Self {
+        Self {
+            value: 50.0,
+            per_hour: 1.0,
+            last_petted_day: None,
+        }
+    }
+}
+
+impl Happiness {
+    pub const MAX: f32 = 100.0;
+    /// At or above this the animal is happy.
+    pub const HAPPY: f32 = 70.0;
+    /// Below this the animal is unhappy.
+    pub const UNHAPPY: f32 = 25.0;
+
+    pub fn is_happy(&self) -> bool {
+        self.value >= Self::HAPPY
+    }
+
+    /// Number of eggs or bottles produced at once: happy animals give an extra one.
+    pub fn produce_count(&self) -> usize {
+        if self.is_happy() { 2 } else { 1 }
+    }
+
+    pub fn is_unhappy(&self) -> bool {
+        self.value < Self::UNHAPPY
+    }
+
+    pub fn add(&mut self, amount: f32) {
+        self.value = (self.value + amount).clamp(0.0, Self::MAX);
+    }
+}
+
+/// Tiled tile layer whose tiles animals graze on (e.g. the Grass layer).
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct GrazingLayer;
+
+/// Emote shown in a bubble above an animal.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Emote {
+    Hungry,
+    Happy,
+}
+
+/// Position of the emote bubble relative to the animal.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
-#[require(
-    Name = "Cow",
-    Npc,
-    Sprite,
-    CowAnimation,
-    WanderConfig,
-    WalkCycles,
-    IdleTimer,
-    RigidBody::Dynamic,
-    LockedAxes::ROTATION_LOCKED,
-    Speed,  // Set dynamically per walk cycle from WanderConfig
-    YSort = YSort { offset: -8.0 },
-)]
-#[component(on_add = Self::on_add)]
-pub struct Cow;
+pub struct EmoteOffset(pub Vec2);
+
+/// Bubble child showing the animal's current emote.
+#[derive(Component, Default)]
+pub struct EmoteBubble {
+    pub emote: Option<Emote>,
+}
 
-impl Cow {
-    const COLLIDER_OFFSET_Y: f32 = 2.0;
+/// A bundle of grass that can be picked up and fed to animals.
+/// Place it on Tiled point objects.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+#[require(Name = "Grass", Item = Item { kind: ItemKind::Grass })]
+#[component(on_add = Self::on_add)]
+pub struct Grass;
 
+impl Grass {
     fn on_add(mut world: DeferredWorld, ctx: HookContext) {
-        let entity = ctx.entity;
+        let atlas = world.resource::<ProduceAtlas>();
+        let sprite = Sprite::from_atlas_image(
+            atlas.texture.clone(),
+            TextureAtlas {
+                layout: atlas.layout.clone(),
+                index: ProduceAtlas::GRASS,
+            },
+        );
+        world.commands().entity(ctx.entity).insert(sprite);
+    }
+}
 
-        // Get atlas resource
-        let atlas = world
-            .get_resource::<CowAtlas>()
-            .expect("CowAtlas must be present");
+// ─────────────────────────────────────────────────────────────────────────────
+// Eggs
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tracks when a chicken last laid an egg (one per in-game day).
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct EggLayer {
+    pub last_laid_day: Option<u32>,
+}
+
+/// Chicken walking to `nest` (at `position`) to lay an egg.
+#[derive(Component)]
+pub struct GoingToNest {
+    pub nest: Entity,
+    pub position: Vec2,
+}
+
+/// Chicken sitting on `nest` while laying.
+#[derive(Component)]
+pub struct LayingEgg {
+    pub nest: Entity,
+    pub timer: Timer,
+}
 
-        let texture = atlas.texture.clone();
-        let layout = atlas.layout.clone();
+impl LayingEgg {
+    pub const DURATION: f32 = 2.0;
 
-        // Configure sprite
-        if let Some(mut sprite) = world.get_mut::<Sprite>(entity) {
-            sprite.image = texture;
-            sprite.texture_atlas = Some(TextureAtlas { layout, index: 0 });
+    pub fn new(nest: Entity) -> Self {
+        Self {
+            nest,
+            timer: Timer::from_seconds(Self::DURATION, TimerMode::Once),
         }
+    }
+}
 
-        // Custom anchor: cow artwork is bottom-left of 32x32 frame
+/// Nest placed from Tiled where well-fed chickens lay their eggs.
+/// Holds one egg at a time.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+#[require(Name = "Nest", Sprite, TileOccupant = TileOccupant::BOTTOM_LEFT_TILE)]
+#[component(on_add = Self::on_add)]
+pub struct Nest {
+    /// Chicken heading here to lay, or the egg waiting to be collected.
+    #[reflect(ignore)]
+    pub occupant: Option<Entity>,
+}
+
+impl Nest {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        let atlas = world.resource::<NestAtlas>();
+        let sprite = Sprite::from_atlas_image(
+            atlas.texture.clone(),
+            TextureAtlas {
+                layout: atlas.layout.clone(),
+                index: NestAtlas::NEST,
+            },
+        );
+        world.commands().entity(ctx.entity).insert(sprite);
+    }
+}
+
+/// A collectable egg laid in a nest.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+#[require(Name = "Egg", Item = Item { kind: ItemKind::Egg }, EggPop)]
+#[component(on_add = Self::on_add)]
+pub struct Egg;
+
+impl Egg {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        let image = world.resource::<EggImage>().0.clone();
         world
             .commands()
-            .entity(entity)
-            .insert(Anchor(Vec2::new(0.0, -0.25)));
+            .entity(ctx.entity)
+            .insert(Sprite::from_image(image));
+    }
+}
+
+/// Short "pop" scale animation played when an egg appears.
+#[derive(Component)]
+pub struct EggPop(pub Timer);
 
-        // Spawn collider as child (at cow's feet)
-        world.commands().entity(entity).with_child((
-            Collider::circle(6.0),
-            Transform::from_translation(Vec3::new(0.0, Self::COLLIDER_OFFSET_Y, 0.0)),
-            CollisionLayers::new(
-                GameLayer::Npc,
-                [GameLayer::Default, GameLayer::Player, GameLayer::Npc],
-            ),
-        ));
+impl Default for EggPop {
+    fn default() -> Self {
+        Self(Timer::from_seconds(0.3, TimerMode::Once))
     }
 }
 
-/// Animation states for cows.
-#[derive(Component, Reflect, Default, Clone, Copy, PartialEq, Eq)]
+// ─────────────────────────────────────────────────────────────────────────────
+// Milking
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Whether a cow can be milked today. Reset every morning.
+#[derive(Component, Reflect)]
 #[reflect(Component)]
-pub enum CowAnimation {
-    #[default]
-    Idle, // frames 0-2 (row 0, cols 0-2)
-    Walk, // frames 5-6 (row 1, cols 0-1)
+pub struct Milkable {
+    pub available: bool,
 }
 
-impl CharacterAnimation for CowAnimation {
-    fn frames(self) -> (usize, usize) {
-        match self {
-            Self::Idle => (0, 2),
-            Self::Walk => (5, 6),
+impl Default for Milkable {
+    fn default() -> Self {
+        Self { available: true }
+    }
+}
+
+/// Cow standing still while `player` milks it.
+#[derive(Component)]
+pub struct BeingMilked {
+    pub player: Entity,
+    pub timer: Timer,
+}
+
+impl BeingMilked {
+    pub const DURATION: f32 = 1.5;
+
+    pub fn new(player: Entity) -> Self {
+        Self {
+            player,
+            timer: Timer::from_seconds(Self::DURATION, TimerMode::Once),
         }
     }
+}
 
-    fn fps(self) -> u8 {
-        5
+/// A collectable bottle of milk.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+#[require(Name = "Milk", Item = Item { kind: ItemKind::Milk })]
+#[component(on_add = Self::on_add)]
+pub struct Milk;
+
+impl Milk {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        let atlas = world.resource::<ProduceAtlas>();
+        let sprite = Sprite::from_atlas_image(
+            atlas.texture.clone(),
+            TextureAtlas {
+                layout: atlas.layout.clone(),
+                index: ProduceAtlas::MILK,
+            },
+        );
+        world.commands().entity(ctx.entity).insert(sprite);
     }
+}
 
-    fn to_idle(self) -> Self {
-        Self::Idle
+/// Small happy hop of an animal's sprite (moves the anchor, not the body).
+#[derive(Component)]
+pub struct Bounce {
+    pub timer: Timer,
+    /// Anchor before the hop started.
+    pub origin: Option<Vec2>,
+}
+
+impl Bounce {
+    /// Hop height as a fraction of the sprite height.
+    pub const HEIGHT: f32 = 0.1;
+    /// Number of hops.
+    pub const HOPS: f32 = 2.0;
+
+    pub fn new() -> Self {
+        Self {
+            timer: Timer::from_seconds(0.4, TimerMode::Once),
+            origin: None,
+        }
     }
+}
 
-    fn to_walk(self) -> Self {
-        Self::Walk
+// ─────────────────────────────────────────────────────────────────────────────
+// Homes
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Building animals sleep in (e.g. the chicken house), placed from Tiled.
+/// Animals of its species that can reach its entrance are assigned to it
+/// up to `capacity`.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct AnimalHome {
+    pub capacity: u32,
+    /// Species id of the animals living here (e.g. `chicken`).
+    pub species: String,
+    /// `HomeEntrance` animals go in and out through (object reference in
+    /// Tiled, same map). A home without one takes no residents.
+    pub entrance: Option<Entity>,
+    /// Animals assigned to this home.
+    #[reflect(ignore)]
+    pub residents: Vec<Entity>,
+}
+
+impl Default for AnimalHome {
+    fn default() -> Self {
+        Self {
+            capacity: 8,
+            species: String::new(),
+            entrance: None,
+            residents: Vec::new(),
+        }
     }
 }
+
+impl AnimalHome {
+    pub fn has_room(&self) -> bool {
+        self.residents.len() < self.capacity as usize
+    }
+}
+
+/// Point in front of an `AnimalHome` door where animals go in and out.
+/// Must be inside the navigation region of the animals living there.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct HomeEntrance;
+
+/// Home assigned to an animal.
+#[derive(Component)]
+pub struct Home {
+    pub building: Entity,
+    pub entrance: Vec2,
+}
+
+/// Animal walking to its home's entrance for the night.
+#[derive(Component)]
+pub struct GoingHome;
+
+/// Animal inside its home: hidden, without physics, until morning.
+#[derive(Component)]
+pub struct Sheltered;
@@ -1,6 +1,7 @@
 pub mod components;
 mod plugin;
 mod resources;
+pub mod species;
 mod systems;
 
 pub use plugin::AnimalsPlugin;
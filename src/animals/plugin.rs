@@ -1,9 +1,23 @@
 use super::{
-    components::{Chicken, ChickenAnimation, Cow, CowAnimation},
-    resources::{ChickenAtlas, CowAtlas},
-    systems::{hide_tiled_npc_visual, load_chicken_atlas, load_cow_atlas},
+    components::{
+        Animal, AnimalAnimation, AnimalHome, EggLayer, EmoteOffset, Grass, GrazingLayer, Happiness,
+        HomeEntrance, Hunger, Milkable, Nest,
+    },
+    resources::{EggImage, EmoteImages, NestAtlas, ProduceAtlas},
+    species::{AnimalSpecies, AnimalSpeciesLoader},
+    systems::{
+        animate_bounce, animate_egg_pop, apply_species, arrive_at_nest, assign_homes, enter_home,
+        graze, hide_tiled_npc_visual, lay_eggs, load_emote_images, load_nest_atlas,
+        load_produce_atlas, milk_cows, on_animal_interacted, propose_animal_tasks, propose_eating,
+        propose_following, propose_sleep, release_animals, reset_milkable_cows, seek_nest,
+        send_animals_home, spawn_emote_bubbles, update_animal_prompt, update_emotes, update_needs,
+    },
 };
-use crate::core::systems::{on_start_moving, on_stop_moving, sync_animation};
+use crate::behaviour::BehaviourSet;
+use crate::core::systems::{
+    on_start_fleeing, on_start_moving, on_stop_fleeing, on_stop_moving, sync_animation,
+};
+use crate::net::has_authority;
 use crate::npcs::NpcSystemSet;
 use bevy::prelude::*;
 
@@ -11,28 +25,91 @@ pub struct AnimalsPlugin;
 
 impl Plugin for AnimalsPlugin {
     fn build(&self, app: &mut App) {
-        app // Chicken
-            .register_type::<ChickenAtlas>()
-            .register_type::<Chicken>()
-            .register_type::<ChickenAnimation>()
-            // Cow
-            .register_type::<CowAtlas>()
-            .register_type::<Cow>()
-            .register_type::<CowAnimation>()
+        app // Species
+            .init_asset::<AnimalSpecies>()
+            .init_asset_loader::<AnimalSpeciesLoader>()
+            .register_type::<Animal>()
+            .register_type::<AnimalAnimation>()
+            // Needs
+            .register_type::<Hunger>()
+            .register_type::<Happiness>()
+            .register_type::<GrazingLayer>()
+            .register_type::<EmoteOffset>()
+            .register_type::<EmoteImages>()
+            .register_type::<Grass>()
+            // Eggs
+            .register_type::<NestAtlas>()
+            .register_type::<EggImage>()
+            .register_type::<Nest>()
+            .register_type::<EggLayer>()
+            // Milking
+            .register_type::<ProduceAtlas>()
+            .register_type::<Milkable>()
+            // Homes
+            .register_type::<AnimalHome>()
+            .register_type::<HomeEntrance>()
             // Startup - load atlases
-            .add_systems(Startup, (load_chicken_atlas, load_cow_atlas))
+            .add_systems(
+                Startup,
+                (load_nest_atlas, load_produce_atlas, load_emote_images),
+            )
+            .add_systems(Update, apply_species)
+            // Needs - authoritative only, emotes everywhere
+            .add_observer(on_animal_interacted)
+            .add_systems(Update, (update_needs, graze).run_if(has_authority))
+            // Behaviours - what animals want to do, authoritative only
+            .add_systems(
+                FixedUpdate,
+                (
+                    propose_animal_tasks,
+                    propose_eating,
+                    propose_following,
+                    propose_sleep,
+                )
+                    .in_set(BehaviourSet::Propose)
+                    .run_if(has_authority),
+            )
+            .add_systems(
+                Update,
+                (spawn_emote_bubbles, update_emotes, update_animal_prompt),
+            )
+            // Egg laying - authoritative only, between NPC state machine and movement
+            .add_systems(
+                FixedUpdate,
+                (seek_nest, arrive_at_nest, lay_eggs)
+                    .chain()
+                    .after(NpcSystemSet::StateMachine)
+                    .before(NpcSystemSet::Movement)
+                    .run_if(has_authority),
+            )
+            .add_systems(Update, animate_egg_pop)
+            // Milking - authoritative only
+            .add_systems(
+                FixedUpdate,
+                (milk_cows, reset_milkable_cows)
+                    .after(NpcSystemSet::StateMachine)
+                    .before(NpcSystemSet::Movement)
+                    .run_if(has_authority),
+            )
+            .add_systems(Update, animate_bounce)
+            // Homes - authoritative only
+            .add_systems(
+                FixedUpdate,
+                (assign_homes, send_animals_home, enter_home, release_animals)
+                    .chain()
+                    .after(NpcSystemSet::StateMachine)
+                    .before(NpcSystemSet::Movement)
+                    .run_if(has_authority),
+            )
             // Animation - use generic systems from core, run after NPC movement
             .add_systems(
                 FixedUpdate,
                 (
-                    // Chicken animation
-                    on_start_moving::<ChickenAnimation>,
-                    on_stop_moving::<ChickenAnimation>,
-                    sync_animation::<ChickenAnimation>,
-                    // Cow animation
-                    on_start_moving::<CowAnimation>,
-                    on_stop_moving::<CowAnimation>,
-                    sync_animation::<CowAnimation>,
+                    on_start_moving::<AnimalAnimation>,
+                    on_stop_moving::<AnimalAnimation>,
+                    on_start_fleeing::<AnimalAnimation>,
+                    on_stop_fleeing::<AnimalAnimation>,
+                    sync_animation::<AnimalAnimation>,
                 )
                     .after(NpcSystemSet::Movement),
             )
@@ -1,15 +1,38 @@
 use bevy::prelude::*;
 
+/// Atlas for egg_and_nest.png (egg, cracked egg, nest, nest with egg).
 #[derive(Resource, Default, Reflect)]
 #[reflect(Resource)]
-pub struct ChickenAtlas {
+pub struct NestAtlas {
     pub texture: Handle<Image>,
     pub layout: Handle<TextureAtlasLayout>,
 }
 
+impl NestAtlas {
+    pub const NEST: usize = 2;
+}
+
+/// Sprite for collectable eggs.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct EggImage(pub Handle<Image>);
+
+/// Atlas for simple_milk_and_grass_item.png (milk bottles and grass).
 #[derive(Resource, Default, Reflect)]
 #[reflect(Resource)]
-pub struct CowAtlas {
+pub struct ProduceAtlas {
     pub texture: Handle<Image>,
     pub layout: Handle<TextureAtlasLayout>,
 }
+
+impl ProduceAtlas {
+    pub const MILK: usize = 0;
+    pub const GRASS: usize = 3;
+}
+
+/// Generated images for emote bubbles.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct EmoteImages {
+    pub heart: Handle<Image>,
+}
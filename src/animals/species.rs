@@ -0,0 +1,158 @@
+use crate::{behaviour::FleeConfig, npcs::components::WanderConfig};
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::Deserialize;
+
+/// Everything that sets an animal species apart, loaded from
+/// `assets/game/animals/<id>.species.ron`. Applied to `Animal`s once loaded.
+#[derive(Asset, TypePath)]
+pub struct AnimalSpecies {
+    pub name: String,
+    #[dependency]
+    pub texture: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    /// Sprite anchor (e.g. bottom-left to match Tiled tile object placement).
+    pub anchor: Vec2,
+    pub collider: SpeciesCollider,
+    /// Radius of the area players interact with the animal in.
+    pub interaction_radius: f32,
+    pub y_sort_offset: f32,
+    /// Position of emote bubbles relative to the animal.
+    pub emote_offset: Vec2,
+    pub animations: AnimalClips,
+    pub wander: WanderConfig,
+    /// How the animal flees, if it ever does.
+    pub flee: Option<FleeConfig>,
+    /// Whether a player bumping into the animal startles it (needs `flee`).
+    pub startled_by_bumps: bool,
+    pub produce: Option<Produce>,
+}
+
+impl AnimalSpecies {
+    /// Asset path of a species file.
+    pub fn path(id: &str) -> String {
+        format!("game/animals/{id}.species.ron")
+    }
+}
+
+/// Sprite sheet of a species, split into a grid of equally sized frames.
+#[derive(Deserialize)]
+pub struct SpriteSheet {
+    pub path: String,
+    pub frame_size: UVec2,
+    pub columns: u32,
+    pub rows: u32,
+}
+
+/// Body collider, a circle at the animal's feet.
+#[derive(Deserialize, Clone, Copy)]
+pub struct SpeciesCollider {
+    pub radius: f32,
+    pub offset: Vec2,
+}
+
+/// Frames (inclusive) and speed of one animation of the sprite sheet.
+#[derive(Reflect, Deserialize, Clone, Copy, PartialEq)]
+pub struct SpriteClip {
+    pub first: usize,
+    pub last: usize,
+    pub fps: u8,
+}
+
+impl Default for SpriteClip {
+    fn default() -> Self {
+        Self {
+            first: 0,
+            last: 0,
+            fps: 5,
+        }
+    }
+}
+
+/// Animations every species provides.
+#[derive(Reflect, Deserialize, Default, Clone, Copy, PartialEq)]
+pub struct AnimalClips {
+    pub idle: SpriteClip,
+    pub walk: SpriteClip,
+    /// Played while fleeing.
+    pub run: SpriteClip,
+}
+
+/// What an animal gives the farmer.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Produce {
+    /// Lays an egg in a nest once a day.
+    Eggs,
+    /// Can be milked once a day.
+    Milk,
+}
+
+/// Species file contents, before its sprite sheet is loaded.
+#[derive(Deserialize)]
+struct AnimalSpeciesFile {
+    name: String,
+    sprite_sheet: SpriteSheet,
+    anchor: Vec2,
+    collider: SpeciesCollider,
+    interaction_radius: f32,
+    y_sort_offset: f32,
+    emote_offset: Vec2,
+    animations: AnimalClips,
+    wander: WanderConfig,
+    #[serde(default)]
+    flee: Option<FleeConfig>,
+    #[serde(default)]
+    startled_by_bumps: bool,
+    #[serde(default)]
+    produce: Option<Produce>,
+}
+
+/// Loads `.species.ron` files.
+#[derive(Default, TypePath)]
+pub struct AnimalSpeciesLoader;
+
+impl AssetLoader for AnimalSpeciesLoader {
+    type Asset = AnimalSpecies;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: AnimalSpeciesFile = ron::de::from_bytes(&bytes)?;
+
+        let sheet = file.sprite_sheet;
+        let texture = load_context.load(sheet.path);
+        let layout = load_context.add_labeled_asset(
+            "layout".to_string(),
+            TextureAtlasLayout::from_grid(sheet.frame_size, sheet.columns, sheet.rows, None, None),
+        );
+
+        Ok(AnimalSpecies {
+            name: file.name,
+            texture,
+            layout,
+            anchor: file.anchor,
+            collider: file.collider,
+            interaction_radius: file.interaction_radius,
+            y_sort_offset: file.y_sort_offset,
+            emote_offset: file.emote_offset,
+            animations: file.animations,
+            wander: file.wander,
+            flee: file.flee,
+            startled_by_bumps: file.startled_by_bumps,
+            produce: file.produce,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["species.ron"]
+    }
+}
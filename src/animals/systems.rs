@@ -1,56 +1,937 @@
-use super::resources::{ChickenAtlas, CowAtlas};
-use crate::npcs::components::Npc;
-use bevy::prelude::*;
-use bevy_ecs_tiled::prelude::TiledObjectVisuals;
+use super::{
+    components::{
+        Animal, AnimalAnimation, AnimalHome, BeingMilked, Bounce, Egg, EggLayer, EggPop, Emote,
+        EmoteBubble, EmoteOffset, GoingHome, GoingToNest, GrazingLayer, Happiness, Home,
+        HomeEntrance, Hunger, LayingEgg, Milk, Milkable, Nest, Sheltered, SpeciesLoading,
+    },
+    resources::{EggImage, EmoteImages, NestAtlas, ProduceAtlas},
+    species::{AnimalSpecies, Produce},
+};
+use crate::{
+    behaviour::{Behaviour, BehaviourStatus, Brain, Priority},
+    clock::{DayPeriod, DayStarted, GameClock, PeriodStarted},
+    core::components::{Moving, YSort},
+    interactable::{Interactable, InteractableSensor, Interacted},
+    inventory::{Inventory, ItemKind},
+    navigation::{
+        Pathfinder,
+        components::{InNavigationRegion, NavigationPath},
+    },
+    npcs::components::Npc,
+    objects::components::Item,
+    physics::GameLayer,
+    player::{
+        Player,
+        components::{Busy, EquippedTool, PlayerAnimation},
+    },
+    spatial::SpatialGrid,
+    tiled::{TileGrid, TileOccupant},
+};
+use avian2d::prelude::{
+    Collider, ColliderDisabled, CollisionEventsEnabled, CollisionLayers, RigidBodyDisabled,
+};
+use bevy::{
+    asset::{LoadState, RenderAssetUsages},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    sprite::Anchor,
+};
+use bevy_ecs_tiled::prelude::*;
 
-/// Loads the chicken sprite sheet.
-pub fn load_chicken_atlas(
+/// Sets animals up from their species file once it's loaded.
+pub fn apply_species(
+    mut commands: Commands,
+    mut animals: Query<(
+        Entity,
+        &Animal,
+        &SpeciesLoading,
+        &mut Sprite,
+        &mut AnimalAnimation,
+    )>,
+    species_assets: Res<Assets<AnimalSpecies>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (entity, animal, loading, mut sprite, mut animation) in &mut animals {
+        let Some(species) = species_assets.get(&loading.0) else {
+            if let LoadState::Failed(err) = asset_server.load_state(&loading.0) {
+                error!("Animal species '{}' failed to load: {err}", animal.species);
+                commands.entity(entity).remove::<SpeciesLoading>();
+            }
+            continue;
+        };
+
+        sprite.image = species.texture.clone();
+        sprite.texture_atlas = Some(TextureAtlas {
+            layout: species.layout.clone(),
+            index: species.animations.idle.first,
+        });
+        animation.clips = species.animations;
+
+        let mut animal_commands = commands.entity(entity);
+        animal_commands.remove::<SpeciesLoading>().insert((
+            Name::new(species.name.clone()),
+            Anchor(species.anchor),
+            YSort {
+                offset: species.y_sort_offset,
+            },
+            EmoteOffset(species.emote_offset),
+            TileOccupant {
+                offset: species.collider.offset,
+            },
+            species.wander.clone(),
+        ));
+        if let Some(flee) = &species.flee {
+            animal_commands.insert(flee.clone());
+        }
+        match species.produce {
+            Some(Produce::Eggs) => {
+                animal_commands.insert(EggLayer::default());
+            }
+            Some(Produce::Milk) => {
+                animal_commands.insert(Milkable::default());
+            }
+            None => {}
+        }
+
+        // Collider at the animal's feet
+        // No NavMeshObstacle - animals use real-time separation instead
+        let collider = species.collider;
+        let body = commands
+            .spawn((
+                ChildOf(entity),
+                Collider::circle(collider.radius),
+                Transform::from_translation(collider.offset.extend(0.0)),
+                CollisionLayers::new(
+                    GameLayer::Npc,
+                    [
+                        GameLayer::Default,
+                        GameLayer::Player,
+                        GameLayer::Npc,
+                        GameLayer::Interactable,
+                    ],
+                ),
+            ))
+            .id();
+        if species.startled_by_bumps {
+            commands.entity(body).insert(CollisionEventsEnabled);
+        }
+
+        // Interaction area for feeding, petting and produce
+        commands.spawn((
+            ChildOf(entity),
+            InteractableSensor,
+            Collider::circle(species.interaction_radius),
+            Transform::from_translation(collider.offset.extend(0.0)),
+        ));
+    }
+}
+
+/// Hides the Tiled sprite visual for NPCs (we use our own animated sprites).
+pub fn hide_tiled_npc_visual(mut commands: Commands, npcs: Query<&TiledObjectVisuals, Added<Npc>>) {
+    for visuals in &npcs {
+        for visual_entity in visuals.iter() {
+            commands.entity(visual_entity).insert(Visibility::Hidden);
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Needs
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Hunger removed by one bundle of grass.
+const FEED_AMOUNT: f32 = 40.0;
+/// Happiness gained from being fed by hand.
+const FEED_HAPPINESS: f32 = 5.0;
+/// Happiness gained from being petted (once per day).
+const PET_HAPPINESS: f32 = 15.0;
+/// Hunger removed per in-game hour while grazing on grass.
+const GRAZE_PER_HOUR: f32 = 12.0;
+/// Hungry animals notice players carrying grass within this distance...
+const FOLLOW_RANGE: f32 = 64.0;
+/// ...and follow them, stopping this close.
+const FOLLOW_DISTANCE: f32 = 16.0;
+
+/// Minimum dot product between the player's facing and the direction to the
+/// animal for the player to count as facing it.
+const FACING_THRESHOLD: f32 = 0.5;
+
+/// Emote bubbles blink: shown for `EMOTE_SHOWN` seconds every `EMOTE_PERIOD`.
+const EMOTE_PERIOD: f32 = 6.0;
+const EMOTE_SHOWN: f32 = 1.5;
+
+/// Heart drawn for the happy emote, one row per string (top to bottom).
+const HEART: [&str; 6] = [
+    ".##.##.", //
+    "#######", //
+    "#######", //
+    ".#####.", //
+    "..###..", //
+    "...#...", //
+];
+
+/// Generates the small images used by emote bubbles.
+pub fn load_emote_images(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let width = HEART[0].len() as u32;
+    let height = HEART.len() as u32;
+    let data = HEART
+        .iter()
+        .flat_map(|row| row.chars())
+        .flat_map(|pixel| match pixel {
+            '#' => [220, 60, 80, 255],
+            _ => [0, 0, 0, 0],
+        })
+        .collect();
+
+    let heart = images.add(Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ));
+    commands.insert_resource(EmoteImages { heart });
+}
+
+/// Animals get hungrier and, slowly, less happy as in-game time passes.
+/// Being hungry makes them lose happiness twice as fast.
+pub fn update_needs(
+    time: Res<Time>,
+    clock: Res<GameClock>,
+    mut animals: Query<(&mut Hunger, &mut Happiness)>,
+) {
+    let hours = time.delta_secs() * clock.minutes_per_second / 60.0;
+
+    for (mut hunger, mut happiness) in &mut animals {
+        let gloom = if hunger.is_hungry() { 2.0 } else { 1.0 };
+        let (hunger_rate, happiness_rate) = (hunger.per_hour, happiness.per_hour);
+        hunger.add(hunger_rate * hours);
+        happiness.add(-happiness_rate * gloom * hours);
+    }
+}
+
+/// Hungry animals standing on grass stop to eat, until they're sated.
+pub fn propose_eating(
+    grid: Res<TileGrid>,
+    mut animals: Query<(Entity, &mut Brain, &Hunger)>,
+    grazing_layers: Query<(), With<GrazingLayer>>,
+) {
+    for (entity, mut brain, hunger) in &mut animals {
+        let eating = brain.current == Behaviour::Eat;
+        let wants_to_eat = hunger.is_hungry() || (eating && hunger.value > Hunger::SATED);
+        let on_grass = grid.occupant_tile(entity).is_some_and(|tile| {
+            grid.layers_at(tile)
+                .any(|layer| grazing_layers.contains(layer.entity))
+        });
+        if wants_to_eat && on_grass {
+            brain.propose(Priority::EAT, Behaviour::Eat);
+        }
+    }
+}
+
+/// Animals eating grass get less hungry.
+pub fn graze(time: Res<Time>, clock: Res<GameClock>, mut animals: Query<(&Brain, &mut Hunger)>) {
+    let hours = time.delta_secs() * clock.minutes_per_second / 60.0;
+
+    for (brain, mut hunger) in &mut animals {
+        if brain.current == Behaviour::Eat {
+            hunger.add(-GRAZE_PER_HOUR * hours);
+        }
+    }
+}
+
+/// Hungry animals near a player carrying grass follow them (looked up in the
+/// spatial grid around each such player).
+pub fn propose_following(
+    grid: Res<SpatialGrid>,
+    mut animals: Query<(&mut Brain, &Hunger)>,
+    players: Query<(Entity, &Transform, &Inventory), With<Player>>,
+) {
+    for (player, transform, inventory) in &players {
+        if !inventory.contains(ItemKind::Grass) {
+            continue;
+        }
+
+        for (animal, _) in grid.within(transform.translation.truncate(), FOLLOW_RANGE) {
+            let Ok((mut brain, hunger)) = animals.get_mut(animal) else {
+                continue;
+            };
+            if hunger.is_hungry() {
+                brain.propose(
+                    Priority::FOLLOW,
+                    Behaviour::Follow {
+                        target: player,
+                        distance: FOLLOW_DISTANCE,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Animals sleep through the night wherever they are (those with a home
+/// go there first, see `propose_animal_tasks`).
+pub fn propose_sleep(clock: Res<GameClock>, mut animals: Query<&mut Brain, With<Happiness>>) {
+    if clock.period() != DayPeriod::Night {
+        return;
+    }
+
+    for mut brain in &mut animals {
+        brain.propose(Priority::SLEEP, Behaviour::Sleep);
+    }
+}
+
+/// Handles a player interacting with an animal, in order of priority:
+/// - feeds it grass from the player's inventory if it's hungry,
+/// - milks a content cow when the player faces it empty-handed,
+/// - otherwise pets it (once per day).
+pub fn on_animal_interacted(
+    trigger: On<Interacted>,
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    mut animals: Query<(
+        &GlobalTransform,
+        &mut Hunger,
+        &mut Happiness,
+        Option<&Milkable>,
+        Has<BeingMilked>,
+    )>,
+    mut players: Query<
+        (
+            &GlobalTransform,
+            &PlayerAnimation,
+            &EquippedTool,
+            Has<Busy>,
+            &mut Inventory,
+        ),
+        With<Player>,
+    >,
+) {
+    let event = trigger.event();
+    let Ok((animal_transform, mut hunger, mut happiness, milkable, being_milked)) =
+        animals.get_mut(event.entity)
+    else {
+        return;
+    };
+    let Ok((player_transform, animation, tool, busy, mut inventory)) = players.get_mut(event.actor)
+    else {
+        return;
+    };
+
+    if being_milked || busy {
+        return;
+    }
+
+    if hunger.is_hungry() {
+        if inventory.remove(ItemKind::Grass, 1) {
+            hunger.add(-FEED_AMOUNT);
+            happiness.add(FEED_HAPPINESS);
+            commands.entity(event.entity).insert(Bounce::new());
+        }
+        return;
+    }
+
+    let to_animal = (animal_transform.translation() - player_transform.translation())
+        .truncate()
+        .normalize_or_zero();
+    let facing = to_animal.dot(animation.facing()) >= FACING_THRESHOLD;
+
+    if milkable.is_some_and(|m| m.available)
+        && !happiness.is_unhappy()
+        && facing
+        && *tool == EquippedTool::None
+    {
+        commands
+            .entity(event.entity)
+            .insert(BeingMilked::new(event.actor));
+        commands.entity(event.actor).insert(Busy);
+        return;
+    }
+
+    if happiness.last_petted_day != Some(clock.day) {
+        happiness.last_petted_day = Some(clock.day);
+        happiness.add(PET_HAPPINESS);
+        commands.entity(event.entity).insert(Bounce::new());
+    }
+}
+
+/// Shows what interacting with an animal will do.
+pub fn update_animal_prompt(
+    clock: Res<GameClock>,
+    mut animals: Query<(&Hunger, &Happiness, Option<&Milkable>, &mut Interactable)>,
+) {
+    for (hunger, happiness, milkable, mut interactable) in &mut animals {
+        let prompt = if hunger.is_hungry() {
+            "Feed"
+        } else if milkable.is_some_and(|m| m.available) && !happiness.is_unhappy() {
+            "Milk"
+        } else if happiness.last_petted_day != Some(clock.day) {
+            "Pet"
+        } else {
+            "Petted today"
+        };
+        if interactable.prompt != prompt {
+            interactable.prompt = prompt.to_string();
+        }
+    }
+}
+
+/// Gives new animals a hidden emote bubble above their head.
+pub fn spawn_emote_bubbles(
+    mut commands: Commands,
+    animals: Query<(Entity, &EmoteOffset), Added<EmoteOffset>>,
+) {
+    for (entity, offset) in &animals {
+        commands.entity(entity).with_child((
+            Name::new("EmoteBubble"),
+            EmoteBubble::default(),
+            Sprite::default(),
+            Transform::from_translation(offset.0.extend(1.0)),
+            Visibility::Hidden,
+        ));
+    }
+}
+
+/// Pops a grass bubble above hungry animals and a heart above happy ones,
+/// every few seconds.
+pub fn update_emotes(
+    time: Res<Time>,
+    produce_atlas: Res<ProduceAtlas>,
+    emote_images: Res<EmoteImages>,
+    animals: Query<(Entity, &Hunger, &Happiness, &Children)>,
+    mut bubbles: Query<(&mut EmoteBubble, &mut Sprite, &mut Visibility)>,
+) {
+    for (entity, hunger, happiness, children) in &animals {
+        let emote = if hunger.is_hungry() {
+            Some(Emote::Hungry)
+        } else if happiness.is_happy() {
+            Some(Emote::Happy)
+        } else {
+            None
+        };
+
+        // Offset each animal's blink so they don't all emote in unison
+        let phase = (entity.to_bits() % 16) as f32 * EMOTE_PERIOD / 16.0;
+        let shown = (time.elapsed_secs() + phase) % EMOTE_PERIOD < EMOTE_SHOWN;
+
+        let mut iter = bubbles.iter_many_mut(children);
+        while let Some((mut bubble, mut sprite, mut visibility)) = iter.fetch_next() {
+            if bubble.emote != emote {
+                bubble.emote = emote;
+                *sprite = match emote {
+                    Some(Emote::Hungry) => Sprite::from_atlas_image(
+                        produce_atlas.texture.clone(),
+                        TextureAtlas {
+                            layout: produce_atlas.layout.clone(),
+                            index: ProduceAtlas::GRASS,
+                        },
+                    ),
+                    Some(Emote::Happy) => Sprite::from_image(emote_images.heart.clone()),
+                    None => Sprite::default(),
+                };
+                sprite.custom_size = Some(Vec2::splat(8.0));
+            }
+
+            visibility.set_if_neq(if emote.is_some() && shown {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Eggs
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Hours of the day during which well-fed chickens go lay.
+const LAYING_HOURS: (f32, f32) = (6.0, 18.0);
+/// Hunger added by laying an egg.
+const LAYING_HUNGER: f32 = 20.0;
+/// Offset between produce items dropped together, so they don't overlap.
+const PRODUCE_SPACING: Vec3 = Vec3::new(8.0, 0.0, 0.0);
+
+/// Loads the nest sprite sheet and the egg sprite.
+pub fn load_nest_atlas(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    let texture = asset_server.load("game/characters/chicken_sprites.png");
-    // 4 columns x 2 rows, 16x16 per frame
+    let texture = asset_server.load("game/characters/egg_and_nest.png");
+    // 4 columns x 1 row, 16x16 per frame
     let layout = layouts.add(TextureAtlasLayout::from_grid(
         UVec2::splat(16),
         4,
-        2,
+        1,
         None,
         None,
     ));
-    commands.insert_resource(ChickenAtlas { texture, layout });
+    commands.insert_resource(NestAtlas { texture, layout });
+    commands.insert_resource(EggImage(asset_server.load("game/objects/egg_item.png")));
 }
 
-/// Hides the Tiled sprite visual for NPCs (we use our own animated sprites).
-pub fn hide_tiled_npc_visual(
+/// Sends well-fed, content chickens that haven't laid today to the nearest
+/// free nest they can reach, in their navigation region or across links.
+pub fn seek_nest(
     mut commands: Commands,
-    npcs: Query<&TiledObjectVisuals, Added<Npc>>,
+    clock: Res<GameClock>,
+    chickens: Query<
+        (
+            Entity,
+            &Transform,
+            &EggLayer,
+            &Hunger,
+            &Happiness,
+            &InNavigationRegion,
+        ),
+        (Without<GoingToNest>, Without<LayingEgg>),
+    >,
+    mut nests: Query<(Entity, &GlobalTransform, &mut Nest)>,
+    occupants: Query<(), Or<(With<EggLayer>, With<Egg>)>>,
+    pathfinder: Pathfinder,
 ) {
-    for visuals in &npcs {
-        for visual_entity in visuals.iter() {
-            commands.entity(visual_entity).insert(Visibility::Hidden);
+    if !clock.is_between(LAYING_HOURS.0, LAYING_HOURS.1) {
+        return;
+    }
+
+    for (chicken, transform, layer, hunger, happiness, in_region) in &chickens {
+        if layer.last_laid_day == Some(clock.day) || hunger.is_hungry() || happiness.is_unhappy() {
+            continue;
+        }
+
+        if pathfinder.get(in_region.0).is_none() {
+            continue;
+        }
+
+        let current_pos = transform.translation.truncate();
+
+        // Free nests in regions this chicken can get to, nearest first
+        let mut candidates: Vec<(Entity, Vec2)> = nests
+            .iter()
+            .filter(|(_, _, nest)| nest.occupant.is_none_or(|e| !occupants.contains(e)))
+            .map(|(entity, nest_transform, _)| (entity, nest_transform.translation().truncate()))
+            .filter(|(_, pos)| {
+                pathfinder
+                    .region_at(*pos)
+                    .is_some_and(|region| pathfinder.connected(in_region.0, region))
+            })
+            .collect();
+        candidates.sort_by(|(_, a), (_, b)| {
+            a.distance_squared(current_pos)
+                .total_cmp(&b.distance_squared(current_pos))
+        });
+
+        let Some((nest_entity, position)) = candidates
+            .into_iter()
+            .find(|(_, pos)| pathfinder.path(in_region.0, current_pos, *pos).is_some())
+        else {
+            continue;
+        };
+
+        if let Ok((_, _, mut nest)) = nests.get_mut(nest_entity) {
+            nest.occupant = Some(chicken);
+        }
+
+        commands.entity(chicken).insert(GoingToNest {
+            nest: nest_entity,
+            position,
+        });
+    }
+}
+
+/// Sits chickens down once they reach their nest. Chickens that can't get
+/// there give up the nest.
+pub fn arrive_at_nest(
+    mut commands: Commands,
+    chickens: Query<(Entity, &GoingToNest, &Brain)>,
+    mut nests: Query<&mut Nest>,
+) {
+    for (chicken, going, brain) in &chickens {
+        if !brain.is_going_to(going.position) {
+            continue;
+        }
+
+        match brain.status {
+            BehaviourStatus::Running => {}
+            BehaviourStatus::Succeeded => {
+                commands
+                    .entity(chicken)
+                    .remove::<GoingToNest>()
+                    .insert(LayingEgg::new(going.nest));
+            }
+            BehaviourStatus::Failed => {
+                if let Ok(mut nest) = nests.get_mut(going.nest)
+                    && nest.occupant == Some(chicken)
+                {
+                    nest.occupant = None;
+                }
+                commands.entity(chicken).remove::<GoingToNest>();
+            }
+        }
+    }
+}
+
+/// Lays an egg in the nest once the chicken has sat long enough.
+/// Happy chickens lay large eggs. Laying makes the chicken hungrier.
+pub fn lay_eggs(
+    mut commands: Commands,
+    time: Res<Time>,
+    clock: Res<GameClock>,
+    mut chickens: Query<(
+        Entity,
+        &mut LayingEgg,
+        &mut EggLayer,
+        &mut Hunger,
+        &Happiness,
+    )>,
+    mut nests: Query<(&GlobalTransform, &mut Nest)>,
+) {
+    for (chicken, mut laying, mut layer, mut hunger, happiness) in &mut chickens {
+        laying.timer.tick(time.delta());
+        if !laying.timer.is_finished() {
+            continue;
+        }
+
+        commands.entity(chicken).remove::<LayingEgg>();
+        layer.last_laid_day = Some(clock.day);
+        hunger.add(LAYING_HUNGER);
+
+        let kind = if happiness.is_happy() {
+            ItemKind::LargeEgg
+        } else {
+            ItemKind::Egg
+        };
+
+        let Ok((nest_transform, mut nest)) = nests.get_mut(laying.nest) else {
+            continue;
+        };
+
+        // Slightly in front of the nest; the first egg keeps the nest occupied
+        let position = nest_transform.translation() + Vec3::new(0.0, 0.0, 0.1);
+        for i in 0..happiness.produce_count() {
+            let offset = PRODUCE_SPACING * i as f32;
+            let egg = commands
+                .spawn((
+                    Egg,
+                    Item { kind },
+                    Transform::from_translation(position + offset),
+                ))
+                .id();
+            if i == 0 {
+                nest.occupant = Some(egg);
+            }
+        }
+    }
+}
+
+/// Scales freshly laid eggs up with a small overshoot.
+pub fn animate_egg_pop(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut eggs: Query<(Entity, &mut EggPop, &mut Transform)>,
+) {
+    for (entity, mut pop, mut transform) in &mut eggs {
+        pop.0.tick(time.delta());
+
+        let scale = EaseFunction::BackOut.sample_clamped(pop.0.fraction());
+        transform.scale = Vec3::splat(scale.max(0.01));
+
+        if pop.0.is_finished() {
+            transform.scale = Vec3::ONE;
+            commands.entity(entity).remove::<EggPop>();
         }
     }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Cow
+// Milking
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Loads the cow sprite sheet.
-pub fn load_cow_atlas(
+/// Loads the milk and grass sprite sheet.
+pub fn load_produce_atlas(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    let texture = asset_server.load("game/characters/cow_sprites.png");
-    // 5 columns x 2 rows, 32x32 per frame
+    let texture = asset_server.load("game/objects/simple_milk_and_grass_item.png");
+    // 4 columns x 1 row, 16x16 per frame
     let layout = layouts.add(TextureAtlasLayout::from_grid(
-        UVec2::splat(32),
-        5,
-        2,
+        UVec2::splat(16),
+        4,
+        1,
         None,
         None,
     ));
-    commands.insert_resource(CowAtlas { texture, layout });
+    commands.insert_resource(ProduceAtlas { texture, layout });
+}
+
+/// Finishes milking: drops a bottle of milk at the player's feet,
+/// marks the cow as milked for the day and lets both go.
+pub fn milk_cows(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut cows: Query<(Entity, &mut BeingMilked, &mut Milkable, &Happiness)>,
+    players: Query<&GlobalTransform, With<Player>>,
+) {
+    for (cow, mut milking, mut milkable, happiness) in &mut cows {
+        milking.timer.tick(time.delta());
+        if !milking.timer.is_finished() {
+            continue;
+        }
+
+        milkable.available = false;
+        commands
+            .entity(cow)
+            .remove::<BeingMilked>()
+            .insert(Bounce::new());
+
+        let Ok(player_transform) = players.get(milking.player) else {
+            continue;
+        };
+        commands.entity(milking.player).remove::<Busy>();
+
+        // Happy cows give more, larger bottles
+        let kind = if happiness.is_happy() {
+            ItemKind::LargeMilk
+        } else {
+            ItemKind::Milk
+        };
+        for i in 0..happiness.produce_count() {
+            let offset = PRODUCE_SPACING * i as f32;
+            commands.spawn((
+                Milk,
+                Item { kind },
+                Transform::from_translation(player_transform.translation() + offset),
+            ));
+        }
+    }
+}
+
+/// Cows can be milked again every new day.
+pub fn reset_milkable_cows(mut days: MessageReader<DayStarted>, mut cows: Query<&mut Milkable>) {
+    let Some(started) = days.read().last() else {
+        return;
+    };
+    debug!("Day {} started, cows can be milked again", started.day);
+
+    for mut milkable in &mut cows {
+        milkable.available = true;
+    }
+}
+
+/// Hops the sprite of bouncing animals by nudging their anchor.
+pub fn animate_bounce(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut animals: Query<(Entity, &mut Bounce, &mut Anchor)>,
+) {
+    for (entity, mut bounce, mut anchor) in &mut animals {
+        let origin = *bounce.origin.get_or_insert(anchor.0);
+
+        bounce.timer.tick(time.delta());
+        if bounce.timer.is_finished() {
+            anchor.0 = origin;
+            commands.entity(entity).remove::<Bounce>();
+            continue;
+        }
+
+        // Lowering the anchor raises the sprite
+        let phase = bounce.timer.fraction() * Bounce::HOPS * std::f32::consts::PI;
+        anchor.0.y = origin.y - phase.sin().abs() * Bounce::HEIGHT;
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Homes
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Happiness lost by a homed animal that spent the night outside.
+const NIGHT_OUTSIDE_UNHAPPINESS: f32 = 20.0;
+/// Distance from the entrance at which an animal slips inside.
+/// Larger than the waypoint threshold so a crowd at the door doesn't block it.
+const ENTER_DISTANCE: f32 = 10.0;
+
+/// Assigns homeless animals to the nearest home for their species with room
+/// whose entrance is in their navigation region or one linked to it.
+pub fn assign_homes(
+    mut commands: Commands,
+    animals: Query<
+        (Entity, &Animal, &Transform, &InNavigationRegion),
+        (With<Happiness>, Without<Home>),
+    >,
+    residents: Query<&Home>,
+    mut homes: Query<(Entity, &mut AnimalHome)>,
+    entrances: Query<&GlobalTransform, With<HomeEntrance>>,
+    pathfinder: Pathfinder,
+) {
+    // Forget residents that no longer exist or live elsewhere
+    for (building, mut home) in &mut homes {
+        home.residents.retain(|resident| {
+            residents
+                .get(*resident)
+                .is_ok_and(|home| home.building == building)
+        });
+    }
+
+    for (animal, species, transform, in_region) in &animals {
+        let position = transform.translation.truncate();
+
+        let nearest = homes
+            .iter()
+            .filter(|(_, home)| home.species == species.species && home.has_room())
+            .filter_map(|(entity, home)| {
+                let entrance = entrances.get(home.entrance?).ok()?.translation().truncate();
+                let entrance_region = pathfinder.region_at(entrance)?;
+                pathfinder
+                    .connected(in_region.0, entrance_region)
+                    .then_some((entity, entrance))
+            })
+            .min_by(|(_, a), (_, b)| a.distance(position).total_cmp(&b.distance(position)));
+
+        let Some((building, entrance)) = nearest else {
+            continue;
+        };
+        if let Ok((_, mut home)) = homes.get_mut(building) {
+            home.residents.push(animal);
+        }
+        commands.entity(animal).insert(Home { building, entrance });
+    }
+}
+
+/// Proposes the chores animals were given: walking to their nest or home,
+/// sitting still while laying or being milked, sleeping once home.
+pub fn propose_animal_tasks(
+    mut animals: Query<(
+        &mut Brain,
+        Option<&GoingToNest>,
+        Option<&Home>,
+        Has<LayingEgg>,
+        Has<BeingMilked>,
+        Has<GoingHome>,
+        Has<Sheltered>,
+    )>,
+) {
+    for (mut brain, going_to_nest, home, laying, being_milked, going_home, sheltered) in
+        &mut animals
+    {
+        let task = if sheltered {
+            Behaviour::Sleep
+        } else if laying || being_milked {
+            Behaviour::Idle
+        } else if let Some(going) = going_to_nest {
+            Behaviour::GoTo {
+                target: going.position,
+            }
+        } else if let (true, Some(home)) = (going_home, home) {
+            Behaviour::GoTo {
+                target: home.entrance,
+            }
+        } else {
+            continue;
+        };
+        brain.propose(Priority::TASK, task);
+    }
+}
+
+/// Sends animals home when the evening starts.
+pub fn send_animals_home(
+    mut commands: Commands,
+    mut periods: MessageReader<PeriodStarted>,
+    animals: Query<Entity, (With<Home>, Without<Sheltered>)>,
+) {
+    let Some(evening) = periods
+        .read()
+        .find(|started| started.period == DayPeriod::Night)
+    else {
+        return;
+    };
+    debug!("Evening of day {}, animals head home", evening.day);
+
+    for animal in &animals {
+        commands.entity(animal).insert(GoingHome);
+    }
+}
+
+/// Hides animals that reached their home's entrance until morning.
+/// Animals that can't find a way stay out for the night.
+pub fn enter_home(
+    mut commands: Commands,
+    animals: Query<(Entity, &Transform, &Home, &Brain, Option<&Children>), With<GoingHome>>,
+    colliders: Query<(), With<Collider>>,
+) {
+    for (animal, transform, home, brain, children) in &animals {
+        if !brain.is_going_to(home.entrance) {
+            continue;
+        }
+
+        if brain.status == BehaviourStatus::Failed {
+            debug!("{animal} found no way home and stays out tonight");
+            commands.entity(animal).remove::<GoingHome>();
+            continue;
+        }
+
+        let at_door = transform.translation.truncate().distance(home.entrance) <= ENTER_DISTANCE;
+        if !at_door && brain.status != BehaviourStatus::Succeeded {
+            continue;
+        }
+
+        commands
+            .entity(animal)
+            .remove::<(Moving, NavigationPath, GoingHome)>()
+            .insert((Sheltered, RigidBodyDisabled, Visibility::Hidden));
+        for child in children.into_iter().flatten() {
+            if colliders.contains(*child) {
+                commands.entity(*child).insert(ColliderDisabled);
+            }
+        }
+    }
+}
+
+/// Lets sheltered animals out in the morning. Homed animals that spent the
+/// night outside are unhappy about it.
+pub fn release_animals(
+    mut commands: Commands,
+    mut periods: MessageReader<PeriodStarted>,
+    mut animals: Query<(
+        Entity,
+        &Home,
+        &mut Transform,
+        &mut Happiness,
+        Has<Sheltered>,
+        Option<&Children>,
+    )>,
+    colliders: Query<(), With<Collider>>,
+) {
+    if !periods
+        .read()
+        .any(|started| started.period == DayPeriod::Day)
+    {
+        return;
+    }
+
+    for (animal, home, mut transform, mut happiness, sheltered, children) in &mut animals {
+        if !sheltered {
+            debug!("{animal} spent the night outside");
+            happiness.add(-NIGHT_OUTSIDE_UNHAPPINESS);
+            commands.entity(animal).remove::<GoingHome>();
+            continue;
+        }
+
+        transform.translation.x = home.entrance.x;
+        transform.translation.y = home.entrance.y;
+        commands
+            .entity(animal)
+            .remove::<(Sheltered, RigidBodyDisabled)>()
+            .insert(Visibility::Inherited);
+        for child in children.into_iter().flatten() {
+            if colliders.contains(*child) {
+                commands.entity(*child).remove::<ColliderDisabled>();
+            }
+        }
+    }
 }
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Something an NPC can do.
+/// NPC types compose behaviours by proposing them to their `Brain` every tick
+/// (see `BehaviourSet::Propose`); the highest priority proposal runs.
+#[derive(Clone, Copy, PartialEq, Debug, Reflect, Default)]
+pub enum Behaviour {
+    /// Stand still.
+    #[default]
+    Idle,
+    /// Alternate between idling and walking to random points of the region.
+    Wander,
+    /// Run away from a point.
+    Flee { from: Vec2 },
+    /// Walk after an entity, stopping `distance` away from it.
+    Follow { target: Entity, distance: f32 },
+    /// Walk to a point.
+    GoTo { target: Vec2 },
+    /// Stand still and eat.
+    Eat,
+    /// Stand still and sleep.
+    Sleep,
+}
+
+impl Behaviour {
+    /// Whether both are the same behaviour, ignoring their parameters.
+    pub fn same_kind(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// Priorities of behaviour proposals. Higher priorities interrupt lower ones.
+pub struct Priority;
+
+impl Priority {
+    pub const IDLE: u8 = 0;
+    pub const WANDER: u8 = 10;
+    pub const EAT: u8 = 20;
+    pub const FOLLOW: u8 = 30;
+    pub const SLEEP: u8 = 40;
+    /// Chores given to the NPC (laying an egg, being milked, going home...).
+    pub const TASK: u8 = 50;
+    pub const FLEE: u8 = 60;
+}
+
+/// Progress of the current behaviour.
+/// Only behaviours with an end (e.g. GoTo) ever leave `Running`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect, Default)]
+pub enum BehaviourStatus {
+    #[default]
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// Picks what an NPC does from the behaviours proposed this tick.
+/// Switching behaviour stops the NPC so the new one starts from rest.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Brain {
+    pub current: Behaviour,
+    /// Priority the current behaviour was proposed with.
+    pub priority: u8,
+    pub status: BehaviourStatus,
+    /// Proposals collected this tick, cleared on selection.
+    #[reflect(ignore)]
+    pub(super) proposals: Vec<(u8, Behaviour)>,
+}
+
+impl Brain {
+    /// Proposes a behaviour for this tick.
+    pub fn propose(&mut self, priority: u8, behaviour: Behaviour) {
+        self.proposals.push((priority, behaviour));
+    }
+
+    /// Whether the NPC is currently walking to `target`.
+    pub fn is_going_to(&self, target: Vec2) -> bool {
+        self.current == Behaviour::GoTo { target }
+    }
+}
+
+/// Lets an NPC flee when startled: bumped into, approached fast or
+/// frightened by a tool swinging nearby. Tuned per NPC type.
+#[derive(Component, Reflect, Deserialize, Clone)]
+#[reflect(Component, Default)]
+pub struct FleeConfig {
+    /// Distance within which a fast-approaching player is noticed.
+    pub notice_radius: f32,
+    /// Speed a player must close in at to count as a threat. Keep it below
+    /// the player's walking speed (50), or walking at the NPC never counts.
+    pub approach_speed: f32,
+    /// Distance within which a player swinging a tool is frightening.
+    pub tool_radius: f32,
+    /// Seconds without a new scare before calming down.
+    pub cooldown: f32,
+    /// Multiplier applied to the NPC's top wander speed while fleeing.
+    pub speed_multiplier: f32,
+}
+
+impl Default for FleeConfig {
+    fn default() -> Self {
+        Self {
+            notice_radius: 48.0,
+            approach_speed: 45.0,
+            tool_radius: 32.0,
+            cooldown: 2.0,
+            speed_multiplier: 2.0,
+        }
+    }
+}
+
+/// NPC startled by something at `from`, fleeing until the timer ends.
+/// Every new scare moves `from` and restarts the timer.
+#[derive(Component)]
+pub struct Startled {
+    pub from: Vec2,
+    pub timer: Timer,
+}
+
+impl Startled {
+    pub fn new(from: Vec2, duration: f32) -> Self {
+        Self {
+            from,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod components;
+mod plugin;
+mod systems;
+
+pub use components::{Behaviour, BehaviourStatus, Brain, FleeConfig, Priority};
+pub use plugin::{BehaviourPlugin, BehaviourSet};
@@ -0,0 +1,67 @@
+use super::{
+    components::{Behaviour, BehaviourStatus, Brain, FleeConfig},
+    systems::{
+        calm_down, flee, follow, go_to, mark_fleeing, notice_threats, propose_flee,
+        select_behaviour, startle_bumped_npcs,
+    },
+};
+use crate::net::has_authority;
+use crate::npcs::NpcSystemSet;
+use bevy::prelude::*;
+
+/// Steps of the NPC behaviour layer, run in order inside
+/// `NpcSystemSet::StateMachine`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BehaviourSet {
+    /// Systems propose behaviours to NPC brains
+    Propose,
+    /// Brains pick the highest priority proposal
+    Select,
+    /// Behaviours drive their NPCs
+    Act,
+}
+
+pub struct BehaviourPlugin;
+
+impl Plugin for BehaviourPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Behaviour>()
+            .register_type::<BehaviourStatus>()
+            .register_type::<Brain>()
+            .register_type::<FleeConfig>()
+            .configure_sets(
+                FixedUpdate,
+                (
+                    BehaviourSet::Propose,
+                    BehaviourSet::Select,
+                    BehaviourSet::Act,
+                )
+                    .chain()
+                    .in_set(NpcSystemSet::StateMachine),
+            )
+            // Authoritative only, like the rest of the NPC simulation
+            .add_systems(
+                FixedUpdate,
+                (
+                    (startle_bumped_npcs, notice_threats),
+                    calm_down,
+                    propose_flee,
+                )
+                    .chain()
+                    .in_set(BehaviourSet::Propose)
+                    .run_if(has_authority),
+            )
+            .add_systems(
+                FixedUpdate,
+                select_behaviour
+                    .in_set(BehaviourSet::Select)
+                    .run_if(has_authority),
+            )
+            .add_systems(
+                FixedUpdate,
+                (go_to, follow, flee, mark_fleeing)
+                    .in_set(BehaviourSet::Act)
+                    .run_if(has_authority),
+            );
+    }
+}
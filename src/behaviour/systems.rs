@@ -0,0 +1,355 @@
+use super::components::{Behaviour, BehaviourStatus, Brain, FleeConfig, Priority, Startled};
+use crate::{
+    core::components::{Fleeing, Moving, Speed},
+    navigation::{
+        Pathfinder,
+        components::{InNavigationRegion, NavigationPath},
+    },
+    npcs::components::{Npc, WalkCycleProgress, WanderConfig},
+    player::{
+        Player,
+        components::{Chopping, Tiling, Watering},
+    },
+    spatial::SpatialGrid,
+};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+/// Random points tried when picking where to flee.
+const FLEE_CANDIDATES: usize = 8;
+/// A followed target must move this far before the path is recomputed.
+const FOLLOW_REPATH_DISTANCE: f32 = 8.0;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Selection
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Switches each NPC to its highest priority proposal (Idle if none).
+/// On ties the current behaviour keeps running. A new behaviour starts
+/// from rest: the previous one's path is dropped.
+pub fn select_behaviour(mut commands: Commands, mut brains: Query<(Entity, &mut Brain)>) {
+    for (entity, mut brain) in &mut brains {
+        let mut proposals = std::mem::take(&mut brain.proposals);
+
+        let mut chosen = (Priority::IDLE, Behaviour::Idle);
+        for &(priority, behaviour) in &proposals {
+            let keeps_current =
+                behaviour.same_kind(&brain.current) && !chosen.1.same_kind(&brain.current);
+            if priority > chosen.0 || (priority == chosen.0 && keeps_current) {
+                chosen = (priority, behaviour);
+            }
+        }
+        proposals.clear();
+        brain.proposals = proposals;
+
+        let (priority, behaviour) = chosen;
+        if behaviour == brain.current {
+            brain.priority = priority;
+            continue;
+        }
+
+        // Same behaviour with new parameters (e.g. fleeing from a moving threat)
+        // carries on; GoTo to another point starts over.
+        let restarts =
+            !behaviour.same_kind(&brain.current) || matches!(behaviour, Behaviour::GoTo { .. });
+        brain.current = behaviour;
+        brain.priority = priority;
+        if restarts {
+            brain.status = BehaviourStatus::Running;
+            commands
+                .entity(entity)
+                .remove::<(Moving, NavigationPath, WalkCycleProgress)>();
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GoTo
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Walks NPCs to their GoTo point. Succeeds on arrival, fails if the point
+/// can't be reached.
+pub fn go_to(
+    mut commands: Commands,
+    mut npcs: Query<(
+        Entity,
+        &mut Brain,
+        &Transform,
+        &InNavigationRegion,
+        &WanderConfig,
+        Option<&NavigationPath>,
+        Has<Moving>,
+    )>,
+    pathfinder: Pathfinder,
+) {
+    for (npc, mut brain, transform, in_region, config, path, moving) in &mut npcs {
+        let Behaviour::GoTo { target } = brain.current else {
+            continue;
+        };
+        if brain.status != BehaviourStatus::Running {
+            continue;
+        }
+
+        if moving {
+            if path.is_some_and(|path| path.current().is_none()) {
+                brain.status = BehaviourStatus::Succeeded;
+                commands.entity(npc).remove::<(Moving, NavigationPath)>();
+            }
+            continue;
+        }
+
+        if pathfinder.get(in_region.0).is_none() {
+            continue;
+        }
+        let Some(path) = pathfinder.path(in_region.0, transform.translation.truncate(), target)
+        else {
+            brain.status = BehaviourStatus::Failed;
+            continue;
+        };
+
+        commands.entity(npc).insert((
+            Moving,
+            NavigationPath::new(path, target),
+            Speed(config.max_speed),
+        ));
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Follow
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Walks NPCs after their Follow target, stopping close to it.
+/// Fails if the target is gone.
+pub fn follow(
+    mut commands: Commands,
+    mut npcs: Query<(
+        Entity,
+        &mut Brain,
+        &Transform,
+        &InNavigationRegion,
+        &WanderConfig,
+        Option<&NavigationPath>,
+    )>,
+    targets: Query<&GlobalTransform>,
+    pathfinder: Pathfinder,
+) {
+    for (npc, mut brain, transform, in_region, config, path) in &mut npcs {
+        let Behaviour::Follow { target, distance } = brain.current else {
+            continue;
+        };
+        let Ok(target_transform) = targets.get(target) else {
+            brain.status = BehaviourStatus::Failed;
+            continue;
+        };
+
+        let current_pos = transform.translation.truncate();
+        let target_pos = target_transform.translation().truncate();
+
+        // Close enough: wait for the target to move away
+        if current_pos.distance(target_pos) <= distance {
+            if path.is_some() {
+                commands.entity(npc).remove::<(Moving, NavigationPath)>();
+            }
+            continue;
+        }
+
+        // Aim beside the target (its own spot may be carved out of the navmesh)
+        let destination =
+            target_pos + (current_pos - target_pos).normalize_or_zero() * distance * 0.5;
+        if path.is_some_and(|path| {
+            path.current().is_some()
+                && path.destination.distance(destination) < FOLLOW_REPATH_DISTANCE
+        }) {
+            continue;
+        }
+
+        let Some(new_path) = pathfinder.path(in_region.0, current_pos, destination) else {
+            continue;
+        };
+
+        commands.entity(npc).insert((
+            Moving,
+            NavigationPath::new(new_path, destination),
+            Speed(config.max_speed),
+        ));
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Flee
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Startles NPCs that can flee when a player bumps into them.
+pub fn startle_bumped_npcs(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    solid_colliders: Query<(), (With<Collider>, Without<Sensor>)>,
+    npcs: Query<&FleeConfig, With<Npc>>,
+    players: Query<&Transform, With<Player>>,
+) {
+    for evt in collisions.read() {
+        if !solid_colliders.contains(evt.collider1) || !solid_colliders.contains(evt.collider2) {
+            continue;
+        }
+        let (Some(body1), Some(body2)) = (evt.body1, evt.body2) else {
+            continue;
+        };
+
+        // Check both orderings - one is the NPC, the other the player
+        let (npc, player) = if npcs.contains(body1) {
+            (body1, body2)
+        } else if npcs.contains(body2) {
+            (body2, body1)
+        } else {
+            continue;
+        };
+        let (Ok(config), Ok(player_transform)) = (npcs.get(npc), players.get(player)) else {
+            continue;
+        };
+
+        commands.entity(npc).insert(Startled::new(
+            player_transform.translation.truncate(),
+            config.cooldown,
+        ));
+    }
+}
+
+/// Startles NPCs that can flee when a player walks straight at them or swings a
+/// tool close by. Only NPCs in the spatial grid around each player are
+/// checked (sheltered NPCs aren't in it, so don't notice anything).
+pub fn notice_threats(
+    mut commands: Commands,
+    grid: Res<SpatialGrid>,
+    npcs: Query<&FleeConfig, With<Npc>>,
+    players: Query<
+        (
+            &Transform,
+            &LinearVelocity,
+            Has<Chopping>,
+            Has<Tiling>,
+            Has<Watering>,
+        ),
+        With<Player>,
+    >,
+) {
+    // Farthest any NPC notices a threat from
+    let reach = npcs
+        .iter()
+        .map(|config| config.notice_radius.max(config.tool_radius))
+        .fold(0.0, f32::max);
+
+    for (player_transform, velocity, chopping, tiling, watering) in &players {
+        let player_pos = player_transform.translation.truncate();
+        // Only swinging a tool is alarming; milking or petting is not
+        let using_tool = chopping || tiling || watering;
+
+        for (npc, position) in grid.within(player_pos, reach) {
+            let Ok(config) = npcs.get(npc) else {
+                continue;
+            };
+
+            let offset = position - player_pos;
+            let distance = offset.length();
+            // Speed at which the player closes in (negative when moving away)
+            let approach_speed = velocity.0.dot(offset.normalize_or_zero());
+            let approaching =
+                distance <= config.notice_radius && approach_speed >= config.approach_speed;
+            let swinging = using_tool && distance <= config.tool_radius;
+
+            if approaching || swinging {
+                commands
+                    .entity(npc)
+                    .insert(Startled::new(player_pos, config.cooldown));
+            }
+        }
+    }
+}
+
+/// Ends the fright of startled NPCs once their timer runs out.
+pub fn calm_down(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut npcs: Query<(Entity, &mut Startled)>,
+) {
+    for (npc, mut startled) in &mut npcs {
+        startled.timer.tick(time.delta());
+        if startled.timer.is_finished() {
+            commands.entity(npc).remove::<Startled>();
+        }
+    }
+}
+
+/// Startled NPCs flee from what startled them.
+pub fn propose_flee(mut npcs: Query<(&mut Brain, &Startled)>) {
+    for (mut brain, startled) in &mut npcs {
+        brain.propose(
+            Priority::FLEE,
+            Behaviour::Flee {
+                from: startled.from,
+            },
+        );
+    }
+}
+
+/// Runs fleeing NPCs to a point of their region away from the threat,
+/// picking a new one whenever they get there or the threat moves past it.
+pub fn flee(
+    mut commands: Commands,
+    npcs: Query<(
+        Entity,
+        &Brain,
+        &Transform,
+        &InNavigationRegion,
+        &WanderConfig,
+        &FleeConfig,
+        Option<&NavigationPath>,
+    )>,
+    pathfinder: Pathfinder,
+) {
+    for (npc, brain, transform, in_region, wander, config, path) in &npcs {
+        let Behaviour::Flee { from } = brain.current else {
+            continue;
+        };
+        let current_pos = transform.translation.truncate();
+        let current_distance = current_pos.distance(from);
+        if path.is_some_and(|path| {
+            path.current().is_some() && path.destination.distance(from) > current_distance
+        }) {
+            continue;
+        }
+
+        let Some((region, navmesh)) = pathfinder.get(in_region.0) else {
+            continue;
+        };
+
+        // Farthest of a few random points that gets us further away
+        let Some((destination, path)) = (0..FLEE_CANDIDATES)
+            .filter_map(|_| region.random_point())
+            .filter(|point| point.distance(from) > current_distance)
+            .filter_map(|point| Some((point, navmesh.path(current_pos, point)?)))
+            .max_by(|(a, _), (b, _)| a.distance(from).total_cmp(&b.distance(from)))
+        else {
+            continue;
+        };
+
+        commands.entity(npc).insert((
+            Moving,
+            NavigationPath::new(path.path, destination),
+            Speed(wander.max_speed * config.speed_multiplier),
+        ));
+    }
+}
+
+/// Marks NPCs whose current behaviour is Flee, so they play their run animation
+/// (and clients see it).
+pub fn mark_fleeing(mut commands: Commands, npcs: Query<(Entity, &Brain, Has<Fleeing>)>) {
+    for (npc, brain, marked) in &npcs {
+        let fleeing = matches!(brain.current, Behaviour::Flee { .. });
+        if fleeing && !marked {
+            commands.entity(npc).insert(Fleeing);
+        } else if !fleeing && marked {
+            commands.entity(npc).remove::<Fleeing>();
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod plugin;
+mod resources;
+mod systems;
+
+pub use plugin::BenchmarkPlugin;
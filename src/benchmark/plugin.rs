@@ -0,0 +1,73 @@
+use super::{
+    resources::{Benchmark, YSortBenchmark},
+    systems::{
+        Y_SORT_TIME, end_y_sort_timer, spawn_benchmark_animals, spawn_benchmark_objects,
+        start_y_sort_timer,
+    },
+};
+use crate::{core::YSortSystems, net::has_authority};
+use bevy::{
+    diagnostic::{
+        Diagnostic, FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin, RegisterDiagnostic,
+    },
+    prelude::*,
+};
+
+/// Stress tests, logging frame times every second. Without their flags this
+/// plugin does nothing.
+/// - `--benchmark [count]` fills the first navigation region with `count`
+///   animals, for the NPC simulation.
+/// - `--benchmark-y-sort [count]` scatters `count` static objects over it in a
+///   Y-sorted layer, and also logs the time spent Y-sorting.
+pub struct BenchmarkPlugin;
+
+impl BenchmarkPlugin {
+    /// Count following `flag` on the command line, or `default` when the flag
+    /// has none. `None` without the flag.
+    fn parse_count(flag: &str, default: usize) -> Option<usize> {
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            if arg == flag {
+                let count = args.next().and_then(|c| c.parse().ok());
+                return Some(count.unwrap_or(default));
+            }
+        }
+        None
+    }
+}
+
+impl Plugin for BenchmarkPlugin {
+    fn build(&self, app: &mut App) {
+        let animals = Self::parse_count("--benchmark", Benchmark::DEFAULT_COUNT);
+        let objects = Self::parse_count("--benchmark-y-sort", YSortBenchmark::DEFAULT_COUNT);
+        if animals.is_none() && objects.is_none() {
+            return;
+        }
+
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+        app.add_plugins(LogDiagnosticsPlugin::default());
+
+        if let Some(count) = animals {
+            app.insert_resource(Benchmark { remaining: count })
+                .add_systems(Update, spawn_benchmark_animals.run_if(has_authority));
+        }
+
+        if let Some(count) = objects {
+            app.insert_resource(YSortBenchmark {
+                remaining: count,
+                started: None,
+            })
+            .register_diagnostic(Diagnostic::new(Y_SORT_TIME).with_suffix("ms"))
+            .add_systems(Update, spawn_benchmark_objects)
+            .add_systems(
+                PostUpdate,
+                (
+                    start_y_sort_timer.before(YSortSystems),
+                    end_y_sort_timer.after(YSortSystems),
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use std::time::Instant;
+
+/// Benchmark requested on the command line.
+#[derive(Resource)]
+pub struct Benchmark {
+    /// Animals still to spawn.
+    pub remaining: usize,
+}
+
+impl Benchmark {
+    /// Species of the spawned animals.
+    pub const SPECIES: &str = "chicken";
+    /// Animals spawned when `--benchmark` isn't given a count.
+    pub const DEFAULT_COUNT: usize = 300;
+}
+
+/// Y-sort benchmark requested on the command line.
+#[derive(Resource)]
+pub struct YSortBenchmark {
+    /// Static objects still to spawn.
+    pub remaining: usize,
+    /// When this frame's Y-sort started.
+    pub started: Option<Instant>,
+}
+
+impl YSortBenchmark {
+    /// Objects spawned when `--benchmark-y-sort` isn't given a count.
+    pub const DEFAULT_COUNT: usize = 5000;
+    /// Size of an object, about that of a tree.
+    pub const OBJECT_SIZE: Vec2 = Vec2::new(16.0, 32.0);
+}
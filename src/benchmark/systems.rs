@@ -0,0 +1,95 @@
+use super::resources::{Benchmark, YSortBenchmark};
+use crate::{
+    animals::components::Animal, core::components::YSort, navigation::components::NavigationRegion,
+};
+use bevy::{
+    diagnostic::{DiagnosticPath, Diagnostics},
+    prelude::*,
+};
+use std::time::Instant;
+
+/// Time spent in `YSortSystems` each frame (ms).
+pub const Y_SORT_TIME: DiagnosticPath = DiagnosticPath::const_new("y_sort_time");
+
+/// Spawns the benchmark animals at random points of the first navigation
+/// region, once its outline is known.
+pub fn spawn_benchmark_animals(
+    mut commands: Commands,
+    mut benchmark: ResMut<Benchmark>,
+    regions: Query<&NavigationRegion>,
+) {
+    if benchmark.remaining == 0 {
+        return;
+    }
+    let Some(region) = regions.iter().find(|region| region.vertices.len() >= 3) else {
+        return;
+    };
+
+    // Points the region fails to give are retried next frame
+    let points: Vec<Vec2> = (0..benchmark.remaining)
+        .filter_map(|_| region.random_point())
+        .collect();
+    for point in &points {
+        commands.spawn((
+            Animal {
+                species: Benchmark::SPECIES.to_string(),
+            },
+            Transform::from_translation(point.extend(0.0)),
+        ));
+    }
+    info!(
+        "Benchmark: spawned {} {}s",
+        points.len(),
+        Benchmark::SPECIES
+    );
+    benchmark.remaining -= points.len();
+}
+
+/// Scatters the Y-sort benchmark objects over the first navigation region, in
+/// a Y-sorted layer of their own, once its outline is known.
+pub fn spawn_benchmark_objects(
+    mut commands: Commands,
+    mut benchmark: ResMut<YSortBenchmark>,
+    regions: Query<&NavigationRegion>,
+) {
+    if benchmark.remaining == 0 {
+        return;
+    }
+    let Some(region) = regions.iter().find(|region| region.vertices.len() >= 3) else {
+        return;
+    };
+
+    // Points the region fails to give are retried next frame
+    let points: Vec<Vec2> = (0..benchmark.remaining)
+        .filter_map(|_| region.random_point())
+        .collect();
+    commands
+        .spawn((
+            Name::new("Y-sort benchmark"),
+            YSort::default(),
+            Transform::default(),
+            Visibility::default(),
+        ))
+        .with_children(|layer| {
+            for point in &points {
+                layer.spawn((
+                    Sprite::from_color(Color::srgb(0.2, 0.5, 0.2), YSortBenchmark::OBJECT_SIZE),
+                    Transform::from_translation(point.extend(0.0)),
+                ));
+            }
+        });
+    info!("Benchmark: spawned {} static objects", points.len());
+    benchmark.remaining -= points.len();
+}
+
+pub fn start_y_sort_timer(mut benchmark: ResMut<YSortBenchmark>) {
+    benchmark.started = Some(Instant::now());
+}
+
+/// Records the time since `start_y_sort_timer`. Systems outside
+/// `YSortSystems` may run in between, so this is an upper bound.
+pub fn end_y_sort_timer(mut benchmark: ResMut<YSortBenchmark>, mut diagnostics: Diagnostics) {
+    if let Some(started) = benchmark.started.take() {
+        diagnostics.add_measurement(&Y_SORT_TIME, || started.elapsed().as_secs_f64() * 1000.0);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::physics::NonSolidTiledObject;
+use bevy::prelude::*;
+
+/// Tile layer of a building prefab drawn over its interior (the roof). Fades
+/// out while the local player is in the prefab's `InteriorZone`, revealing
+/// whoever is inside, and fades back in when they leave.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct RoofLayer {
+    /// Current opacity, from 0 (hidden) to 1.
+    #[reflect(ignore)]
+    pub opacity: f32,
+}
+
+impl RoofLayer {
+    /// Opacity change per second.
+    pub const FADE_SPEED: f32 = 4.0;
+}
+
+impl Default for RoofLayer {
+    fn default() -> Self {
+        Self { opacity: 1.0 }
+    }
+}
+
+/// Inside of a building prefab. Spawned from Tiled as a rectangle in the
+/// prefab's map, whose `RoofLayer`s it hides.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+#[require(NonSolidTiledObject)]
+pub struct InteriorZone {
+    /// Area relative to the object's origin (populated from Tiled).
+    #[reflect(ignore)]
+    pub rect: Rect,
+}
+
+impl InteriorZone {
+    /// Whether a world position is inside the zone, placed by the object's
+    /// transform.
+    pub fn contains(&self, transform: &GlobalTransform, position: Vec2) -> bool {
+        let local = transform
+            .affine()
+            .inverse()
+            .transform_point3(position.extend(0.0));
+        self.rect.contains(local.truncate())
+    }
+}
@@ -0,0 +1,16 @@
+use super::{
+    components::{InteriorZone, RoofLayer},
+    systems::{apply_roof_opacity, fade_roofs, populate_interior_zone_rect},
+};
+use bevy::prelude::*;
+
+pub struct BuildingPlugin;
+
+impl Plugin for BuildingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RoofLayer>()
+            .register_type::<InteriorZone>()
+            .add_observer(populate_interior_zone_rect)
+            .add_systems(Update, (fade_roofs, apply_roof_opacity).chain());
+    }
+}
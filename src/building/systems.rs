@@ -0,0 +1,76 @@
+use super::components::{InteriorZone, RoofLayer};
+use crate::player::LocalPlayer;
+use bevy::{platform::collections::HashSet, prelude::*};
+use bevy_ecs_tiled::prelude::*;
+
+/// Populates InteriorZone rectangles from Tiled object data.
+pub fn populate_interior_zone_rect(
+    trigger: On<TiledEvent<ObjectCreated>>,
+    mut zones: Query<&mut InteriorZone>,
+    map_assets: Res<Assets<TiledMapAsset>>,
+) {
+    let event = trigger.event();
+
+    let Ok(mut zone) = zones.get_mut(event.origin) else {
+        return;
+    };
+    let Some(object) = event.get_object(&map_assets) else {
+        return;
+    };
+
+    let tiled::ObjectShape::Rect { width, height } = object.shape else {
+        warn!("InteriorZone object is not a rectangle");
+        return;
+    };
+
+    // Tiled rectangles extend right and down from the object's origin
+    zone.rect = Rect::new(0.0, -height, width, 0.0);
+}
+
+/// Fades roofs out while the local player is inside their building, and back
+/// in once they leave.
+pub fn fade_roofs(
+    time: Res<Time>,
+    players: Query<&GlobalTransform, With<LocalPlayer>>,
+    zones: Query<(&InteriorZone, &GlobalTransform, &TiledMapReference)>,
+    mut roofs: Query<(&mut RoofLayer, &TiledMapReference)>,
+) {
+    // Building prefabs the local player is inside
+    let occupied: HashSet<Entity> = zones
+        .iter()
+        .filter(|(zone, transform, _)| {
+            players
+                .iter()
+                .any(|player| zone.contains(transform, player.translation().truncate()))
+        })
+        .map(|(_, _, map)| map.0)
+        .collect();
+
+    let step = RoofLayer::FADE_SPEED * time.delta_secs();
+    for (mut roof, map) in &mut roofs {
+        let target = if occupied.contains(&map.0) { 0.0 } else { 1.0 };
+        if roof.opacity != target {
+            roof.opacity += (target - roof.opacity).clamp(-step, step);
+        }
+    }
+}
+
+/// Applies roof opacity to the tiles of roof layers.
+pub fn apply_roof_opacity(
+    roofs: Query<(Entity, &RoofLayer), Changed<RoofLayer>>,
+    children: Query<&Children>,
+    tilemaps: Query<&TileStorage>,
+    mut tiles: Query<&mut TileColor>,
+) {
+    for (layer, roof) in &roofs {
+        for tilemap in children.iter_descendants(layer) {
+            let Ok(storage) = tilemaps.get(tilemap) else {
+                continue;
+            };
+            let mut iter = tiles.iter_many_mut(storage.iter().flatten());
+            while let Some(mut color) = iter.fetch_next() {
+                color.0.set_alpha(roof.opacity);
+            }
+        }
+    }
+}
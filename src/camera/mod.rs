@@ -2,3 +2,4 @@ mod plugin;
 mod systems;
 
 pub use plugin::CameraPlugin;
+pub use systems::GameCursor;
@@ -1,10 +1,11 @@
 use bevy::{
     camera::{RenderTarget, visibility::RenderLayers},
+    ecs::system::SystemParam,
     prelude::*,
     render::render_resource::{
         Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
     },
-    window::WindowResized,
+    window::{PrimaryWindow, WindowResized},
 };
 
 /// The game's native resolution (viewport size)
@@ -23,6 +24,44 @@ pub struct ScreenCamera;
 #[derive(Component)]
 pub struct Canvas;
 
+/// Mouse cursor mapped into the game world: through the screen camera onto
+/// the scaled canvas, then through the game camera.
+#[derive(SystemParam)]
+pub struct GameCursor<'w, 's> {
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    screen_camera: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<ScreenCamera>>,
+    canvas: Query<'w, 's, &'static GlobalTransform, With<Canvas>>,
+    game_camera: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<GameCamera>>,
+}
+
+impl GameCursor<'_, '_> {
+    /// World position under the cursor, if it is over the window.
+    pub fn world_position(&self) -> Option<Vec2> {
+        let cursor = self.windows.single().ok()?.cursor_position()?;
+
+        let (screen_camera, screen_transform) = self.screen_camera.single().ok()?;
+        let screen_pos = screen_camera
+            .viewport_to_world_2d(screen_transform, cursor)
+            .ok()?;
+
+        // Canvas sprite space (undoing its integer scale), centered on the texture
+        let canvas = self.canvas.single().ok()?;
+        let local = canvas
+            .affine()
+            .inverse()
+            .transform_point3(screen_pos.extend(0.0));
+        let viewport_pos = Vec2::new(
+            local.x + VIEWPORT_WIDTH as f32 / 2.0,
+            VIEWPORT_HEIGHT as f32 / 2.0 - local.y,
+        );
+
+        let (game_camera, game_transform) = self.game_camera.single().ok()?;
+        game_camera
+            .viewport_to_world_2d(game_transform, viewport_pos)
+            .ok()
+    }
+}
+
 /// Layer for the canvas (separate from game world)
 const CANVAS_LAYER: RenderLayers = RenderLayers::layer(1);
 
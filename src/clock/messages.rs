@@ -0,0 +1,16 @@
+use super::resources::DayPeriod;
+use bevy::prelude::*;
+
+/// Written when the in-game clock rolls over to a new day.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct DayStarted {
+    pub day: u32,
+}
+
+/// Written when the in-game clock moves into a new part of the day
+/// (morning or evening).
+#[derive(Message, Clone, Copy, Debug)]
+pub struct PeriodStarted {
+    pub day: u32,
+    pub period: DayPeriod,
+}
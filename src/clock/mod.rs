@@ -0,0 +1,8 @@
+pub mod messages;
+mod plugin;
+pub mod resources;
+mod systems;
+
+pub use messages::{DayStarted, PeriodStarted};
+pub use plugin::ClockPlugin;
+pub use resources::{DayPeriod, GameClock};
@@ -0,0 +1,20 @@
+use super::{
+    messages::{DayStarted, PeriodStarted},
+    resources::{DayPeriod, GameClock},
+    systems::advance_clock,
+};
+use crate::net::has_authority;
+use bevy::prelude::*;
+
+pub struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GameClock>()
+            .register_type::<DayPeriod>()
+            .init_resource::<GameClock>()
+            .add_message::<DayStarted>()
+            .add_message::<PeriodStarted>()
+            .add_systems(Update, advance_clock.run_if(has_authority));
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+/// Minutes in an in-game day.
+pub const MINUTES_PER_DAY: f32 = 24.0 * 60.0;
+
+/// Part of the in-game day.
+#[derive(Reflect, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DayPeriod {
+    /// From `GameClock::MORNING` to `GameClock::EVENING`.
+    Day,
+    /// From `GameClock::EVENING` to the next `GameClock::MORNING`.
+    Night,
+}
+
+/// In-game calendar and time of day.
+/// Advanced by the authoritative simulation and replicated to clients.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct GameClock {
+    /// Current day, starting at 1.
+    pub day: u32,
+    /// Minutes since midnight, in `0.0..MINUTES_PER_DAY`.
+    pub minutes: f32,
+    /// In-game minutes that pass per real second.
+    pub minutes_per_second: f32,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            day: 1,
+            minutes: 8.0 * 60.0,
+            minutes_per_second: 1.0,
+        }
+    }
+}
+
+impl GameClock {
+    /// Hour at which the day starts.
+    pub const MORNING: f32 = 6.0;
+    /// Hour at which the night starts.
+    pub const EVENING: f32 = 19.0;
+
+    /// Time of day in hours (e.g. 13.5 for 13:30).
+    pub fn hours(&self) -> f32 {
+        self.minutes / 60.0
+    }
+
+    /// Whether the time of day is in `[from, to)` hours.
+    /// Ranges that wrap past midnight (e.g. 20.0 to 6.0) are supported.
+    pub fn is_between(&self, from: f32, to: f32) -> bool {
+        let hours = self.hours();
+        if from <= to {
+            (from..to).contains(&hours)
+        } else {
+            hours >= from || hours < to
+        }
+    }
+
+    /// Whether it's currently day or night.
+    pub fn period(&self) -> DayPeriod {
+        if self.is_between(Self::MORNING, Self::EVENING) {
+            DayPeriod::Day
+        } else {
+            DayPeriod::Night
+        }
+    }
+}
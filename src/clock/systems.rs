@@ -0,0 +1,31 @@
+use super::{
+    messages::{DayStarted, PeriodStarted},
+    resources::{GameClock, MINUTES_PER_DAY},
+};
+use bevy::prelude::*;
+
+/// Advances the in-game clock, rolling over to the next day at midnight
+/// and announcing mornings and evenings.
+pub fn advance_clock(
+    time: Res<Time>,
+    mut clock: ResMut<GameClock>,
+    mut writer: MessageWriter<DayStarted>,
+    mut period_writer: MessageWriter<PeriodStarted>,
+) {
+    let previous = clock.period();
+    clock.minutes += time.delta_secs() * clock.minutes_per_second;
+
+    while clock.minutes >= MINUTES_PER_DAY {
+        clock.minutes -= MINUTES_PER_DAY;
+        clock.day += 1;
+        writer.write(DayStarted { day: clock.day });
+    }
+
+    let period = clock.period();
+    if period != previous {
+        period_writer.write(PeriodStarted {
+            day: clock.day,
+            period,
+        });
+    }
+}
@@ -116,11 +116,25 @@ pub struct YSort {
     pub offset: f32,
 }
 
+/// Point an entity is Y-sorted by, as a Y offset from its origin, overriding the
+/// offset of its `YSort` (or its layer's). For tall multi-tile objects like houses,
+/// whose origin isn't where other entities pass in front of or behind them.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct YSortAnchor {
+    pub y: f32,
+}
+
 /// Marker: entity is currently moving.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
 pub struct Moving;
 
+/// Marker: entity is running away from something (plays its run animation).
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Fleeing;
+
 /// Trait for animation enums that support idle/walk states.
 pub trait CharacterAnimation: Component<Mutability = Mutable> + Copy + PartialEq {
     /// Returns (first_frame, last_frame) for this animation state.
@@ -137,4 +151,8 @@ pub trait CharacterAnimation: Component<Mutability = Mutable> + Copy + PartialEq
     fn to_idle(self) -> Self;
     /// Convert to walk animation.
     fn to_walk(self) -> Self;
+    /// Convert to run animation (defaults to walking).
+    fn to_run(self) -> Self {
+        self.to_walk()
+    }
 }
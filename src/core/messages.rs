@@ -1,4 +1,6 @@
 use crate::objects::components::Object;
+use crate::player::Player;
+use crate::tiled::TileCoord;
 use crate::tools::components::Tool;
 use bevy::prelude::*;
 use moonshine_kind::Instance;
@@ -24,3 +26,24 @@ impl<T: Tool> Hit<T> {
         }
     }
 }
+
+/// Message fired when a tile tool (hoe, watering can) is used on the tile
+/// the player targets. Only sent when the tool can be used there.
+#[derive(Message)]
+pub struct TileHit<T: Tool> {
+    /// The tile the tool was used on.
+    pub tile: TileCoord,
+    /// The player using the tool.
+    pub player: Instance<Player>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Tool> TileHit<T> {
+    pub fn new(tile: TileCoord, player: Instance<Player>) -> Self {
+        Self {
+            tile,
+            player,
+            _marker: PhantomData,
+        }
+    }
+}
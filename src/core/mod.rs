@@ -3,4 +3,4 @@ pub mod messages;
 mod plugin;
 pub mod systems;
 
-pub use plugin::CorePlugin;
+pub use plugin::{CorePlugin, YSortSystems};
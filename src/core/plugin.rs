@@ -1,12 +1,19 @@
 use super::components::{
-    Active, AnimationFinished, Damage, Health, Moving, SequenceAnimation, Speed, SpriteAnimation,
-    YSort,
+    Active, AnimationFinished, Damage, Fleeing, Health, Moving, SequenceAnimation, Speed,
+    SpriteAnimation, YSort, YSortAnchor,
+};
+use super::messages::{Hit, TileHit};
+use super::systems::{
+    animate_sequences, animate_sprites, apply_eased_y_sort, apply_y_sort, y_sort_changed_layers,
 };
-use super::messages::Hit;
-use super::systems::{animate_sequences, animate_sprites, apply_y_sort};
 use crate::tools::components::{Axe, Hoe, WateringCan};
+use avian2d::prelude::*;
 use bevy::prelude::*;
 
+/// Sets Z from Y in `PostUpdate`, for entities not eased between fixed ticks.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct YSortSystems;
+
 pub struct CorePlugin;
 
 impl Plugin for CorePlugin {
@@ -15,15 +22,27 @@ impl Plugin for CorePlugin {
             .register_type::<SequenceAnimation>()
             .register_type::<AnimationFinished>()
             .register_type::<Moving>()
+            .register_type::<Fleeing>()
             .register_type::<Speed>()
             .register_type::<Health>()
             .register_type::<Damage>()
             .register_type::<Active>()
             .register_type::<YSort>()
+            .register_type::<YSortAnchor>()
             .add_message::<Hit<Axe>>()
-            .add_message::<Hit<Hoe>>()
-            .add_message::<Hit<WateringCan>>()
+            .add_message::<TileHit<Hoe>>()
+            .add_message::<TileHit<WateringCan>>()
+            .configure_sets(PostUpdate, YSortSystems.before(TransformSystems::Propagate))
             .add_systems(Update, (animate_sprites, animate_sequences))
-            .add_systems(PostUpdate, apply_y_sort);
+            .add_systems(
+                FixedLast,
+                apply_eased_y_sort.before(TransformEasingSystems::UpdateEnd),
+            )
+            .add_systems(
+                PostUpdate,
+                (y_sort_changed_layers, apply_y_sort)
+                    .chain()
+                    .in_set(YSortSystems),
+            );
     }
 }
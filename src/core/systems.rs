@@ -1,25 +1,78 @@
 use super::components::{
-    AnimationFinished, CharacterAnimation, Moving, SequenceAnimation, SpriteAnimation, YSort,
+    AnimationFinished, CharacterAnimation, Fleeing, Moving, SequenceAnimation, SpriteAnimation,
+    YSort, YSortAnchor,
 };
+use avian2d::prelude::*;
 use bevy::prelude::*;
 use moonshine_kind::Instance;
 
-/// Updates Z coordinate based on Y for entities in Y-sorted layers or with YSort directly.
+/// Sets Z from Y, sorting by `offset` above the origin. Only writes when Z moves,
+/// so sorted entities don't count as changed again on the next frame.
+fn set_y_sort_z(transform: &mut Mut<Transform>, offset: f32) {
+    let z = -(transform.translation.y + offset);
+    if transform.translation.z != z {
+        transform.translation.z = z;
+    }
+}
+
+/// Updates Z coordinate based on Y for entities in Y-sorted layers or with YSort directly,
+/// when they move, are parented, or change anchor; static objects are sorted once.
+/// Entities eased between fixed ticks are sorted by `apply_eased_y_sort` instead.
 pub fn apply_y_sort(
-    y_sorted_layers: Query<(Instance<YSort>, &YSort)>,
-    mut children: Query<(&ChildOf, &mut Transform), Without<YSort>>,
-    mut y_sorted_entities: Query<(&mut Transform, &YSort)>,
+    y_sorted_layers: Query<&YSort>,
+    mut children: Query<
+        (&ChildOf, &mut Transform, Option<&YSortAnchor>),
+        (
+            Without<YSort>,
+            Or<(Changed<Transform>, Changed<ChildOf>, Changed<YSortAnchor>)>,
+        ),
+    >,
+    mut y_sorted_entities: Query<
+        (&mut Transform, &YSort, Option<&YSortAnchor>),
+        (
+            Or<(Changed<Transform>, Changed<YSort>, Changed<YSortAnchor>)>,
+            Or<(Without<TransformInterpolation>, With<NoTransformEasing>)>,
+        ),
+    >,
 ) {
     // Y-sort children of YSort layers
-    for (child_of, mut transform) in &mut children {
-        if let Ok((_, y_sort)) = y_sorted_layers.get(child_of.parent()) {
-            transform.translation.z = -(transform.translation.y + y_sort.offset);
+    for (child_of, mut transform, anchor) in &mut children {
+        if let Ok(y_sort) = y_sorted_layers.get(child_of.parent()) {
+            set_y_sort_z(&mut transform, anchor.map_or(y_sort.offset, |a| a.y));
         }
     }
 
     // Y-sort entities with YSort directly
-    for (mut transform, y_sort) in &mut y_sorted_entities {
-        transform.translation.z = -(transform.translation.y + y_sort.offset);
+    for (mut transform, y_sort, anchor) in &mut y_sorted_entities {
+        set_y_sort_z(&mut transform, anchor.map_or(y_sort.offset, |a| a.y));
+    }
+}
+
+/// Y-sorts every child of layers that just got `YSort` or changed its offset,
+/// as `apply_y_sort` only sees children that changed themselves.
+pub fn y_sort_changed_layers(
+    layers: Query<(&YSort, &Children), Changed<YSort>>,
+    mut children: Query<(&mut Transform, Option<&YSortAnchor>), Without<YSort>>,
+) {
+    for (y_sort, layer_children) in &layers {
+        let mut iter = children.iter_many_mut(layer_children);
+        while let Some((mut transform, anchor)) = iter.fetch_next() {
+            set_y_sort_z(&mut transform, anchor.map_or(y_sort.offset, |a| a.y));
+        }
+    }
+}
+
+/// Updates Z coordinate based on Y at the end of each fixed tick for entities
+/// with transform interpolation. Z is then eased along with Y, so sorting follows
+/// the interpolated position; setting it after easing would count as a teleport.
+pub fn apply_eased_y_sort(
+    mut y_sorted_entities: Query<
+        (&mut Transform, &YSort, Option<&YSortAnchor>),
+        (With<TransformInterpolation>, Without<NoTransformEasing>),
+    >,
+) {
+    for (mut transform, y_sort, anchor) in &mut y_sorted_entities {
+        set_y_sort_z(&mut transform, anchor.map_or(y_sort.offset, |a| a.y));
     }
 }
 
@@ -84,10 +137,37 @@ pub fn animate_sequences(
     }
 }
 
-/// Generic system: when Moving added, switch to walk animation.
-pub fn on_start_moving<A: CharacterAnimation>(mut query: Query<&mut A, Added<Moving>>) {
+/// Generic system: when Moving added, switch to walk (or run, if fleeing) animation.
+pub fn on_start_moving<A: CharacterAnimation>(
+    mut query: Query<(&mut A, Has<Fleeing>), Added<Moving>>,
+) {
+    for (mut anim, fleeing) in &mut query {
+        *anim = if fleeing {
+            (*anim).to_run()
+        } else {
+            (*anim).to_walk()
+        };
+    }
+}
+
+/// Generic system: when Fleeing added to a moving entity, switch to run animation.
+pub fn on_start_fleeing<A: CharacterAnimation>(
+    mut query: Query<&mut A, (Added<Fleeing>, With<Moving>)>,
+) {
     for mut anim in &mut query {
-        *anim = (*anim).to_walk();
+        *anim = (*anim).to_run();
+    }
+}
+
+/// Generic system: when Fleeing removed from a moving entity, switch back to walk animation.
+pub fn on_stop_fleeing<A: CharacterAnimation>(
+    mut query: Query<&mut A, With<Moving>>,
+    mut removed: RemovedComponents<Fleeing>,
+) {
+    for entity in removed.read() {
+        if let Ok(mut anim) = query.get_mut(entity) {
+            *anim = (*anim).to_walk();
+        }
     }
 }
 
@@ -1,4 +1,8 @@
-use crate::interactable::{Interactable, InteractableSensor};
+use crate::{
+    interactable::{ActorFilter, Interactable, InteractableSensor},
+    inventory::ItemKind,
+    tiled::TileOccupant,
+};
 use avian2d::prelude::*;
 use bevy::{
     ecs::{lifecycle::HookContext, world::DeferredWorld},
@@ -7,9 +11,20 @@ use bevy::{
 
 /// Door entity with open/close functionality.
 /// Visual and collider are defined in Tiled, this component adds animation behavior.
+/// Doors open automatically by default, for players and animals alike, and stay
+/// open while anyone is still inside the sensor. Set the `Interactable` mode to
+/// Manual in Tiled to open and close them with the interact key instead.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component, Default)]
-#[require(Name = "Door", DoorState, Interactable)]
+#[require(
+    Name = "Door",
+    DoorState,
+    TileOccupant = TileOccupant::BOTTOM_LEFT_TILE,
+    Interactable = Interactable {
+        actors: ActorFilter::PlayersAndNpcs,
+        ..Interactable::automatic()
+    },
+)]
 #[component(on_add = Self::on_add)]
 pub struct Door;
 
@@ -38,4 +53,85 @@ pub enum DoorState {
     Open,
     Opening,
     Closing,
+    /// Closed and won't open until unlocked (by key or schedule).
+    Locked,
+}
+
+/// Door that starts locked and opens only for an actor carrying `key`.
+/// Once unlocked it stays unlocked and this component is removed.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+#[require(Door)]
+#[component(on_add = Self::on_add)]
+pub struct DoorLock {
+    /// Item needed to unlock the door.
+    pub key: ItemKind,
+    /// Whether unlocking uses up the key.
+    pub consume_key: bool,
+}
+
+impl Default for DoorLock {
+    fn default() -> Self {
+        Self {
+            key: ItemKind::Key,
+            consume_key: false,
+        }
+    }
+}
+
+impl DoorLock {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        if let Some(mut state) = world.get_mut::<DoorState>(ctx.entity) {
+            *state = DoorState::Locked;
+        }
+    }
+}
+
+/// Opening hours of the in-game clock (e.g. a shop open from 9.0 to 17.0).
+/// Outside these hours the door locks as soon as it is closed.
+/// Hours may wrap past midnight (e.g. from 20.0 to 6.0).
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+#[require(Door)]
+pub struct DoorSchedule {
+    pub opens_at: f32,
+    pub closes_at: f32,
+}
+
+impl Default for DoorSchedule {
+    fn default() -> Self {
+        Self {
+            opens_at: 9.0,
+            closes_at: 17.0,
+        }
+    }
+}
+
+/// Short shake played when someone tries a locked door.
+#[derive(Component)]
+pub struct DoorRattle {
+    pub timer: Timer,
+    /// Horizontal position of the visual before the shake started.
+    pub origin_x: Option<f32>,
 }
+
+impl DoorRattle {
+    pub const DURATION: f32 = 0.3;
+    /// Maximum horizontal offset of the shake in pixels.
+    pub const AMPLITUDE: f32 = 1.0;
+    /// Shakes per second.
+    pub const FREQUENCY: f32 = 20.0;
+
+    pub fn new() -> Self {
+        Self {
+            timer: Timer::from_seconds(Self::DURATION, TimerMode::Once),
+            origin_x: None,
+        }
+    }
+}
+
+/// Collision layers the door's physical collider had before the door first
+/// opened, restored when it closes again.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct DoorColliderLayers(pub CollisionLayers);
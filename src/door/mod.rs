@@ -3,4 +3,5 @@ mod plugin;
 mod resources;
 mod systems;
 
+pub use components::{Door, DoorRattle, DoorState};
 pub use plugin::DoorPlugin;
@@ -1,11 +1,14 @@
 use super::{
-    components::{Door, DoorState},
+    components::{Door, DoorColliderLayers, DoorLock, DoorSchedule, DoorState},
     resources::DoorAtlas,
     systems::{
-        load_door_atlas, on_door_animation_finished, on_door_closing, on_door_opening,
-        on_interactable_activated, on_interactable_deactivated, on_start_closing, on_start_opening,
+        animate_door_rattle, apply_door_schedules, close_vacated_doors, load_door_atlas,
+        on_door_animation_finished, on_door_closing, on_door_interacted, on_door_opening,
+        on_interactable_activated, on_locked_door_occupied, on_start_closing, on_start_opening,
+        sync_door_links, update_door_prompt,
     },
 };
+use crate::net::has_authority;
 use bevy::prelude::*;
 
 pub struct DoorPlugin;
@@ -14,18 +17,31 @@ impl Plugin for DoorPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Door>()
             .register_type::<DoorState>()
+            .register_type::<DoorColliderLayers>()
+            .register_type::<DoorLock>()
+            .register_type::<DoorSchedule>()
             .register_type::<DoorAtlas>()
             .add_systems(Startup, load_door_atlas)
+            .add_observer(on_door_interacted)
             .add_systems(
                 Update,
                 (
-                    on_interactable_activated,
-                    on_interactable_deactivated,
+                    // State transitions only run on the authoritative simulation
+                    (
+                        on_interactable_activated,
+                        on_locked_door_occupied,
+                        close_vacated_doors,
+                        on_door_animation_finished,
+                        apply_door_schedules,
+                        sync_door_links.after(apply_door_schedules),
+                    )
+                        .run_if(has_authority),
                     on_start_opening,
                     on_start_closing,
-                    on_door_animation_finished,
                     on_door_opening,
                     on_door_closing,
+                    animate_door_rattle,
+                    update_door_prompt,
                 ),
             );
     }
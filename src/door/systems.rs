@@ -1,10 +1,18 @@
 use super::{
-    components::{Door, DoorState},
+    components::{Door, DoorColliderLayers, DoorLock, DoorRattle, DoorSchedule, DoorState},
     resources::DoorAtlas,
 };
 use crate::{
+    clock::GameClock,
     core::components::{AnimationFinished, SequenceAnimation},
-    interactable::{InteractableActive, InteractableSensor},
+    interactable::{
+        Interactable, InteractableActive, InteractableOccupants, InteractableSensor, Interacted,
+        InteractionMode,
+    },
+    inventory::Inventory,
+    navigation::components::NavigationLink,
+    physics::GameLayer,
+    player::Player,
 };
 use avian2d::prelude::*;
 use bevy::prelude::*;
@@ -53,13 +61,14 @@ pub fn on_start_opening(
             });
         }
 
-        // Add animation to visual child
+        // Add animation to visual child (clearing any previous finish state)
         commands
             .entity(visual_entity)
             .insert(SequenceAnimation::once(
                 Door::OPEN_FRAMES.to_vec(),
                 Door::FPS,
-            ));
+            ))
+            .remove::<AnimationFinished>();
     }
 }
 
@@ -88,16 +97,45 @@ pub fn on_start_closing(
             });
         }
 
-        // Add animation to visual child
+        // Add animation to visual child (clearing any previous finish state)
         commands
             .entity(visual_entity)
             .insert(SequenceAnimation::once(
                 Door::CLOSE_FRAMES.to_vec(),
                 Door::FPS,
-            ));
+            ))
+            .remove::<AnimationFinished>();
     }
 }
 
+/// The door's physical collider (not the sensor).
+type DoorColliders<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Collider, &'static Position, &'static Rotation),
+    Without<InteractableSensor>,
+>;
+
+/// Whether a player or NPC stands inside the door's physical collider,
+/// where closing the door would trap them.
+fn is_doorway_blocked(
+    children: &Children,
+    colliders: &DoorColliders,
+    spatial_query: &SpatialQuery,
+) -> bool {
+    let filter = SpatialQueryFilter::from_mask([GameLayer::Player, GameLayer::Npc]);
+
+    children.iter().any(|child| {
+        colliders
+            .get(child)
+            .is_ok_and(|(collider, position, rotation)| {
+                !spatial_query
+                    .shape_intersections(collider, position.0, rotation.as_radians(), &filter)
+                    .is_empty()
+            })
+    })
+}
+
 /// Updates DoorState when animation finishes.
 pub fn on_door_animation_finished(
     mut commands: Commands,
@@ -105,26 +143,22 @@ pub fn on_door_animation_finished(
         (Instance<TiledObjectVisualOf>, &ChildOf),
         (With<TiledObjectVisualOf>, Added<AnimationFinished>),
     >,
-    mut doors: Query<(&mut DoorState, Has<InteractableActive>)>,
+    mut doors: Query<(&mut DoorState, &Interactable, &InteractableOccupants)>,
 ) {
     for (visual, child_of) in &visuals_finished {
         // The parent of visual is the door entity
-        let Ok((mut state, has_active)) = doors.get_mut(child_of.parent()) else {
+        let Ok((mut state, interactable, occupants)) = doors.get_mut(child_of.parent()) else {
             continue;
         };
+        let automatic = interactable.mode == InteractionMode::Automatic;
 
         // Update state based on what animation just finished
-        // If player left during opening or entered during closing, immediately reverse
+        // Doors always settle open; `close_vacated_doors` closes them once nobody is around.
+        // Automatic doors: if someone entered during closing, immediately reverse
         match *state {
-            DoorState::Opening => {
-                if has_active {
-                    *state = DoorState::Open;
-                } else {
-                    *state = DoorState::Closing;
-                }
-            }
+            DoorState::Opening => *state = DoorState::Open,
             DoorState::Closing => {
-                if has_active {
+                if automatic && !occupants.is_empty() {
                     *state = DoorState::Opening;
                 } else {
                     *state = DoorState::Closed;
@@ -141,10 +175,14 @@ pub fn on_door_animation_finished(
     }
 }
 
-/// Disables door collider when door starts opening.
+/// Disables door collider when door starts opening, remembering its layers.
 pub fn on_door_opening(
+    mut commands: Commands,
     doors: Query<(Instance<Door>, &DoorState, &Children), Changed<DoorState>>,
-    mut colliders: Query<&mut CollisionLayers, (With<Collider>, Without<InteractableSensor>)>,
+    mut colliders: Query<
+        (Entity, &mut CollisionLayers, Has<DoorColliderLayers>),
+        (With<Collider>, Without<InteractableSensor>),
+    >,
 ) {
     for (_door, state, children) in &doors {
         if *state != DoorState::Opening {
@@ -153,17 +191,23 @@ pub fn on_door_opening(
 
         // Find the physical collider child (not the sensor) and disable it
         for child in children.iter() {
-            if let Ok(mut layers) = colliders.get_mut(child) {
+            if let Ok((entity, mut layers, saved)) = colliders.get_mut(child) {
+                if !saved {
+                    commands.entity(entity).insert(DoorColliderLayers(*layers));
+                }
                 *layers = CollisionLayers::NONE;
             }
         }
     }
 }
 
-/// Re-enables door collider when door starts closing.
+/// Restores the door collider's original layers when door starts closing.
 pub fn on_door_closing(
     doors: Query<(Instance<Door>, &DoorState, &Children), Changed<DoorState>>,
-    mut colliders: Query<&mut CollisionLayers, (With<Collider>, Without<InteractableSensor>)>,
+    mut colliders: Query<
+        (&mut CollisionLayers, &DoorColliderLayers),
+        (With<Collider>, Without<InteractableSensor>),
+    >,
 ) {
     for (_door, state, children) in &doors {
         if *state != DoorState::Closing {
@@ -172,32 +216,242 @@ pub fn on_door_closing(
 
         // Find the physical collider child and re-enable it
         for child in children.iter() {
-            if let Ok(mut layers) = colliders.get_mut(child) {
-                *layers = CollisionLayers::new(LayerMask::ALL, LayerMask::ALL);
+            if let Ok((mut layers, original)) = colliders.get_mut(child) {
+                *layers = original.0;
             }
         }
     }
 }
 
-/// Opens door when player enters interactable area.
-pub fn on_interactable_activated(mut doors: Query<&mut DoorState, Added<InteractableActive>>) {
-    for mut state in &mut doors {
-        if *state == DoorState::Closed {
+/// Opens automatic doors when the first actor enters the interactable area.
+pub fn on_interactable_activated(
+    mut doors: Query<(&mut DoorState, &Interactable), Added<InteractableActive>>,
+) {
+    for (mut state, interactable) in &mut doors {
+        if interactable.mode == InteractionMode::Automatic && *state == DoorState::Closed {
             *state = DoorState::Opening;
         }
     }
 }
 
-/// Closes door when player exits interactable area.
-pub fn on_interactable_deactivated(
-    mut doors: Query<&mut DoorState, With<Door>>,
-    mut removed: RemovedComponents<InteractableActive>,
+/// Closes open automatic doors once nobody is in the interactable area
+/// and the doorway is clear.
+pub fn close_vacated_doors(
+    mut doors: Query<
+        (&mut DoorState, &Interactable, &Children),
+        (With<Door>, Without<InteractableActive>),
+    >,
+    colliders: DoorColliders,
+    spatial_query: SpatialQuery,
 ) {
-    for entity in removed.read() {
-        if let Ok(mut state) = doors.get_mut(entity)
+    for (mut state, interactable, children) in &mut doors {
+        if interactable.mode == InteractionMode::Automatic
             && *state == DoorState::Open
+            && !is_doorway_blocked(children, &colliders, &spatial_query)
         {
             *state = DoorState::Closing;
         }
     }
 }
+
+/// Toggles manual doors when a player interacts with them.
+/// A door won't close while someone stands in the doorway, and a locked
+/// door opens only if the actor carries its key.
+pub fn on_door_interacted(
+    trigger: On<Interacted>,
+    mut commands: Commands,
+    mut doors: Query<(&mut DoorState, &Children, Option<&DoorLock>), With<Door>>,
+    mut inventories: Query<&mut Inventory>,
+    colliders: DoorColliders,
+    spatial_query: SpatialQuery,
+) {
+    let event = trigger.event();
+    let Ok((mut state, children, lock)) = doors.get_mut(event.entity) else {
+        return;
+    };
+
+    match *state {
+        DoorState::Closed => *state = DoorState::Opening,
+        DoorState::Open if !is_doorway_blocked(children, &colliders, &spatial_query) => {
+            *state = DoorState::Closing;
+        }
+        DoorState::Locked => {
+            let inventory = inventories.get_mut(event.actor).ok();
+            if !try_unlock(&mut commands, event.entity, lock, inventory) {
+                commands
+                    .entity(event.entity)
+                    .insert_if_new(DoorRattle::new());
+                return;
+            }
+            *state = DoorState::Opening;
+        }
+        _ => return,
+    }
+    debug!(
+        "{} toggled door {} to {:?}",
+        event.actor, event.entity, *state
+    );
+}
+
+/// Unlocks a key-locked door if `inventory` holds its key, using the key up
+/// if the lock says so. Doors locked by their schedule can't be unlocked.
+fn try_unlock(
+    commands: &mut Commands,
+    door: Entity,
+    lock: Option<&DoorLock>,
+    inventory: Option<Mut<Inventory>>,
+) -> bool {
+    let (Some(lock), Some(mut inventory)) = (lock, inventory) else {
+        return false;
+    };
+    if !inventory.contains(lock.key) {
+        return false;
+    }
+
+    if lock.consume_key {
+        inventory.remove(lock.key, 1);
+    }
+    commands.entity(door).remove::<DoorLock>();
+    true
+}
+
+/// Lets players into locked automatic doors when one of them carries the key,
+/// and rattles the door when nobody does.
+pub fn on_locked_door_occupied(
+    mut commands: Commands,
+    mut doors: Query<
+        (
+            Entity,
+            &mut DoorState,
+            &Interactable,
+            &InteractableOccupants,
+            Option<&DoorLock>,
+        ),
+        Changed<InteractableOccupants>,
+    >,
+    mut inventories: Query<&mut Inventory, With<Player>>,
+) {
+    for (entity, mut state, interactable, occupants, lock) in &mut doors {
+        if interactable.mode != InteractionMode::Automatic || *state != DoorState::Locked {
+            continue;
+        }
+
+        let players: Vec<Entity> = occupants
+            .iter()
+            .filter(|&occupant| inventories.contains(occupant))
+            .collect();
+        if players.is_empty() {
+            continue;
+        }
+
+        let unlocked = players.into_iter().any(|player| {
+            let inventory = inventories.get_mut(player).ok();
+            try_unlock(&mut commands, entity, lock, inventory)
+        });
+
+        if unlocked {
+            *state = DoorState::Opening;
+        } else {
+            commands.entity(entity).insert_if_new(DoorRattle::new());
+        }
+    }
+}
+
+/// Locks scheduled doors outside their opening hours (once closed) and
+/// unlocks them when the hours start.
+pub fn apply_door_schedules(
+    clock: Res<GameClock>,
+    mut doors: Query<
+        (
+            &mut DoorState,
+            &DoorSchedule,
+            &Interactable,
+            Has<InteractableActive>,
+        ),
+        Without<DoorLock>,
+    >,
+) {
+    for (mut state, schedule, interactable, active) in &mut doors {
+        let open_hours = clock.is_between(schedule.opens_at, schedule.closes_at);
+
+        match *state {
+            DoorState::Locked if open_hours => {
+                let automatic = interactable.mode == InteractionMode::Automatic;
+                *state = if automatic && active {
+                    DoorState::Opening
+                } else {
+                    DoorState::Closed
+                };
+            }
+            DoorState::Closed if !open_hours => *state = DoorState::Locked,
+            _ => {}
+        }
+    }
+}
+
+/// Enables navigation links through doors NPCs can pass (automatic doors
+/// open for them) and disables them while the door is locked or shut by hand.
+pub fn sync_door_links(
+    doors: Query<(&DoorState, &Interactable)>,
+    mut links: Query<&mut NavigationLink>,
+) {
+    for mut link in &mut links {
+        let Some(door) = link.door else {
+            continue;
+        };
+        let Ok((state, interactable)) = doors.get(door) else {
+            continue;
+        };
+
+        let enabled = match state {
+            DoorState::Locked => false,
+            DoorState::Open | DoorState::Opening => true,
+            DoorState::Closed | DoorState::Closing => {
+                interactable.mode == InteractionMode::Automatic
+            }
+        };
+        if link.enabled != enabled {
+            link.enabled = enabled;
+        }
+    }
+}
+
+/// Shakes the door visual while it rattles.
+pub fn animate_door_rattle(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut doors: Query<(Entity, &mut DoorRattle, &TiledObjectVisuals)>,
+    mut transforms: Query<&mut Transform>,
+) {
+    for (entity, mut rattle, visuals) in &mut doors {
+        let Some(mut transform) = visuals.first().and_then(|&v| transforms.get_mut(v).ok()) else {
+            continue;
+        };
+        let origin_x = *rattle.origin_x.get_or_insert(transform.translation.x);
+
+        rattle.timer.tick(time.delta());
+        if rattle.timer.is_finished() {
+            transform.translation.x = origin_x;
+            commands.entity(entity).remove::<DoorRattle>();
+            continue;
+        }
+
+        let phase = rattle.timer.elapsed_secs() * DoorRattle::FREQUENCY * std::f32::consts::TAU;
+        transform.translation.x = origin_x + phase.sin() * DoorRattle::AMPLITUDE;
+    }
+}
+
+/// Keeps the prompt of manual doors in sync with their state.
+pub fn update_door_prompt(mut doors: Query<(&DoorState, Has<DoorRattle>, &mut Interactable)>) {
+    for (state, rattling, mut interactable) in &mut doors {
+        let prompt = match state {
+            DoorState::Locked if rattling => "Locked",
+            DoorState::Locked => "Unlock",
+            DoorState::Closed | DoorState::Closing => "Open",
+            DoorState::Open | DoorState::Opening => "Close",
+        };
+        if interactable.prompt != prompt {
+            interactable.prompt = prompt.to_string();
+        }
+    }
+}
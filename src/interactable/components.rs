@@ -1,26 +1,138 @@
 use crate::physics::GameLayer;
 use avian2d::prelude::*;
 use bevy::prelude::*;
+use moonshine_kind::Instance;
+
+/// How an interactable reacts to actors in its sensor.
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InteractionMode {
+    /// Reacts to a deliberate interact press (shows a prompt, fires `Interacted`).
+    #[default]
+    Manual,
+    /// Reacts to proximity alone (e.g. doors opening when walked into).
+    Automatic,
+}
+
+/// Which actors count as occupants of an interactable.
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ActorFilter {
+    /// Only players.
+    #[default]
+    Players,
+    /// Players and NPCs (animals, villagers).
+    PlayersAndNpcs,
+}
 
 /// Marks an entity as interactable. Add a Collider child for the sensor area.
-/// When player enters the sensor, `InteractableActive` is added.
-/// When player exits, `InteractableActive` is removed.
-#[derive(Component, Reflect, Default)]
+/// Actors inside the sensor are tracked in `InteractableOccupants`;
+/// `InteractableActive` is added when the first one enters and removed when
+/// the last one leaves.
+/// Manual interactables are focused by nearby players and receive `Interacted`
+/// when the interact key is pressed.
+#[derive(Component, Reflect)]
 #[reflect(Component, Default)]
-pub struct Interactable;
+#[require(InteractableOccupants)]
+pub struct Interactable {
+    pub mode: InteractionMode,
+    /// Actors that occupy the interactable when inside its sensor.
+    pub actors: ActorFilter,
+    /// Higher priority wins focus when several interactables overlap,
+    /// ties go to the nearest one.
+    pub priority: i32,
+    /// Action shown in the prompt (e.g. "Open", "Talk").
+    pub prompt: String,
+}
+
+impl Default for Interactable {
+    fn default() -> Self {
+        Self {
+            mode: InteractionMode::Manual,
+            actors: ActorFilter::Players,
+            priority: 0,
+            prompt: "Interact".to_string(),
+        }
+    }
+}
+
+impl Interactable {
+    /// Interactable that reacts to proximity instead of the interact key.
+    pub fn automatic() -> Self {
+        Self {
+            mode: InteractionMode::Automatic,
+            ..default()
+        }
+    }
+}
 
-/// Marker: player is currently in the interactable area.
+/// Marker: at least one actor is currently in the interactable area.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
 pub struct InteractableActive;
 
-/// Sensor collider for detecting player proximity.
+/// Actors currently inside the interactable area, in order of arrival.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct InteractableOccupants(Vec<Entity>);
+
+impl InteractableOccupants {
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds an occupant. Returns true if it wasn't already inside.
+    pub(super) fn insert(&mut self, entity: Entity) -> bool {
+        if self.contains(entity) {
+            return false;
+        }
+        self.0.push(entity);
+        true
+    }
+
+    /// Removes an occupant. Returns true if it was inside.
+    pub(super) fn remove(&mut self, entity: Entity) -> bool {
+        let len = self.0.len();
+        self.0.retain(|&e| e != entity);
+        self.0.len() != len
+    }
+
+    /// Drops occupants that no longer pass `keep` (e.g. despawned actors).
+    pub(super) fn retain(&mut self, keep: impl FnMut(&Entity) -> bool) {
+        self.0.retain(keep);
+    }
+}
+
+/// Sensor collider for detecting actor proximity.
 /// Add this as a child of an Interactable entity.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component, Default)]
 #[require(
     Sensor,
     CollisionEventsEnabled,
-    CollisionLayers = CollisionLayers::new(GameLayer::Interactable, GameLayer::Player),
+    CollisionLayers = CollisionLayers::new(
+        GameLayer::Interactable,
+        [GameLayer::Player, GameLayer::Npc],
+    ),
 )]
 pub struct InteractableSensor;
+
+/// The manual interactable an actor would interact with right now, if any.
+#[derive(Component, Reflect, Default, PartialEq)]
+#[reflect(Component)]
+pub struct InteractionFocus(pub Option<Instance<Interactable>>);
+
+/// Prompt shown above the local player's focused interactable.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct InteractionPrompt;
@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+/// Fired when an actor deliberately interacts with a manual interactable.
+///
+/// Written as a message for systems and triggered as an entity event, so
+/// interactables can react with an observer (`.observe(...)` or a global one).
+#[derive(Message, EntityEvent, Clone, Copy, Debug)]
+pub struct Interacted {
+    /// The interactable entity.
+    pub entity: Entity,
+    /// The entity that interacted (usually a player).
+    pub actor: Entity,
+}
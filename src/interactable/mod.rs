@@ -1,6 +1,11 @@
 mod components;
+mod messages;
 mod plugin;
 mod systems;
 
-pub use components::{Interactable, InteractableActive, InteractableSensor};
+pub use components::{
+    ActorFilter, Interactable, InteractableActive, InteractableOccupants, InteractableSensor,
+    InteractionFocus, InteractionMode,
+};
+pub use messages::Interacted;
 pub use plugin::InteractablePlugin;
@@ -1,7 +1,16 @@
 use super::{
-    components::{Interactable, InteractableActive, InteractableSensor},
-    systems::{on_player_enter_interactable, on_player_exit_interactable},
+    components::{
+        ActorFilter, Interactable, InteractableActive, InteractableOccupants, InteractableSensor,
+        InteractionFocus, InteractionMode, InteractionPrompt,
+    },
+    messages::Interacted,
+    systems::{
+        handle_interact_requests, on_actor_enter_interactable, on_actor_exit_interactable,
+        prune_interactable_occupants, spawn_interaction_prompt, update_interaction_focus,
+        update_interaction_prompt,
+    },
 };
+use crate::net::has_authority;
 use bevy::prelude::*;
 
 pub struct InteractablePlugin;
@@ -10,10 +19,30 @@ impl Plugin for InteractablePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Interactable>()
             .register_type::<InteractableActive>()
+            .register_type::<InteractableOccupants>()
+            .register_type::<ActorFilter>()
             .register_type::<InteractableSensor>()
+            .register_type::<InteractionMode>()
+            .register_type::<InteractionFocus>()
+            .register_type::<InteractionPrompt>()
+            .add_message::<Interacted>()
+            .add_systems(Startup, spawn_interaction_prompt)
             .add_systems(
                 Update,
-                (on_player_enter_interactable, on_player_exit_interactable),
+                (
+                    (
+                        on_actor_enter_interactable,
+                        on_actor_exit_interactable,
+                        prune_interactable_occupants,
+                    )
+                        .chain(),
+                    update_interaction_focus,
+                    (
+                        handle_interact_requests.run_if(has_authority),
+                        update_interaction_prompt,
+                    ),
+                )
+                    .chain(),
             );
     }
 }
@@ -1,19 +1,35 @@
-use super::components::{Interactable, InteractableActive, InteractableSensor};
-use crate::player::Player;
+use super::{
+    components::{
+        ActorFilter, Interactable, InteractableActive, InteractableOccupants, InteractableSensor,
+        InteractionFocus, InteractionMode, InteractionPrompt,
+    },
+    messages::Interacted,
+};
+use crate::{
+    npcs::components::Npc,
+    player::{LocalPlayer, Player, messages::Interact},
+};
 use avian2d::prelude::*;
 use bevy::prelude::*;
+use moonshine_kind::Instance;
 
-/// Adds InteractableActive when player enters an interactable sensor.
-pub fn on_player_enter_interactable(
+/// Gap between the top of the sensor and the prompt.
+const PROMPT_MARGIN: f32 = 4.0;
+/// Z for the prompt so it draws above Y-sorted entities.
+const PROMPT_Z: f32 = 900.0;
+
+/// Records actors entering an interactable sensor.
+/// Adds InteractableActive when the first occupant arrives.
+pub fn on_actor_enter_interactable(
     mut commands: Commands,
     mut collision_events: MessageReader<CollisionStart>,
     sensors: Query<&ChildOf, With<InteractableSensor>>,
-    players: Query<(), With<Player>>,
-    interactables: Query<(), (With<Interactable>, Without<InteractableActive>)>,
+    actors: Query<Has<Npc>, Or<(With<Player>, With<Npc>)>>,
+    mut interactables: Query<(&Interactable, &mut InteractableOccupants)>,
 ) {
     for evt in collision_events.read() {
-        // Check which is sensor and which is player
-        let (sensor_collider, player_body) = if sensors.contains(evt.collider1) {
+        // Check which is sensor and which is the actor
+        let (sensor_collider, actor_body) = if sensors.contains(evt.collider1) {
             (evt.collider1, evt.body2)
         } else if sensors.contains(evt.collider2) {
             (evt.collider2, evt.body1)
@@ -21,22 +37,29 @@ pub fn on_player_enter_interactable(
             continue;
         };
 
-        // Check if other body is player
-        let Some(player_body) = player_body else {
+        // Check if other body is an actor
+        let Some(actor_body) = actor_body else {
             continue;
         };
-        if !players.contains(player_body) {
+        let Ok(is_npc) = actors.get(actor_body) else {
             continue;
-        }
+        };
 
         // Get the interactable parent
         let Ok(child_of) = sensors.get(sensor_collider) else {
             continue;
         };
         let interactable_entity = child_of.parent();
+        let Ok((interactable, mut occupants)) = interactables.get_mut(interactable_entity) else {
+            continue;
+        };
+
+        if is_npc && interactable.actors == ActorFilter::Players {
+            continue;
+        }
 
-        // Add active marker if not already active
-        if interactables.contains(interactable_entity) {
+        // Only the first occupant activates the interactable
+        if occupants.insert(actor_body) && occupants.len() == 1 {
             commands
                 .entity(interactable_entity)
                 .insert(InteractableActive);
@@ -44,17 +67,17 @@ pub fn on_player_enter_interactable(
     }
 }
 
-/// Removes InteractableActive when player exits an interactable sensor.
-pub fn on_player_exit_interactable(
+/// Records actors leaving an interactable sensor.
+/// Removes InteractableActive when the last occupant leaves.
+pub fn on_actor_exit_interactable(
     mut commands: Commands,
     mut collision_events: MessageReader<CollisionEnd>,
     sensors: Query<&ChildOf, With<InteractableSensor>>,
-    players: Query<(), With<Player>>,
-    interactables: Query<(), (With<Interactable>, With<InteractableActive>)>,
+    mut interactables: Query<&mut InteractableOccupants, With<Interactable>>,
 ) {
     for evt in collision_events.read() {
-        // Check which is sensor and which is player
-        let (sensor_collider, player_body) = if sensors.contains(evt.collider1) {
+        // Check which is sensor and which is the actor
+        let (sensor_collider, actor_body) = if sensors.contains(evt.collider1) {
             (evt.collider1, evt.body2)
         } else if sensors.contains(evt.collider2) {
             (evt.collider2, evt.body1)
@@ -62,25 +85,141 @@ pub fn on_player_exit_interactable(
             continue;
         };
 
-        // Check if other body is player
-        let Some(player_body) = player_body else {
+        let Some(actor_body) = actor_body else {
             continue;
         };
-        if !players.contains(player_body) {
-            continue;
-        }
 
         // Get the interactable parent
         let Ok(child_of) = sensors.get(sensor_collider) else {
             continue;
         };
         let interactable_entity = child_of.parent();
+        let Ok(mut occupants) = interactables.get_mut(interactable_entity) else {
+            continue;
+        };
 
-        // Remove active marker
-        if interactables.contains(interactable_entity) {
+        // Only the last occupant deactivates the interactable
+        if occupants.remove(actor_body) && occupants.is_empty() {
             commands
                 .entity(interactable_entity)
                 .remove::<InteractableActive>();
         }
     }
 }
+
+/// Drops occupants that were despawned (or lost their actor marker) while
+/// inside, since no CollisionEnd is guaranteed for them.
+pub fn prune_interactable_occupants(
+    mut commands: Commands,
+    mut interactables: Query<(Entity, &mut InteractableOccupants), With<InteractableActive>>,
+    actors: Query<(), Or<(With<Player>, With<Npc>)>>,
+) {
+    for (entity, mut occupants) in &mut interactables {
+        if occupants.iter().all(|occupant| actors.contains(occupant)) {
+            continue;
+        }
+
+        occupants.retain(|&occupant| actors.contains(occupant));
+        if occupants.is_empty() {
+            commands.entity(entity).remove::<InteractableActive>();
+        }
+    }
+}
+
+/// Picks the manual interactable each player would interact with among the
+/// ones they occupy: highest priority first, then nearest.
+pub fn update_interaction_focus(
+    mut players: Query<(Entity, &GlobalTransform, &mut InteractionFocus), With<Player>>,
+    sensors: Query<(&ChildOf, &ColliderAabb), With<InteractableSensor>>,
+    interactables: Query<
+        (
+            Instance<Interactable>,
+            &Interactable,
+            &InteractableOccupants,
+        ),
+        With<InteractableActive>,
+    >,
+) {
+    for (player, transform, mut focus) in &mut players {
+        let position = transform.translation().truncate();
+
+        let best = sensors
+            .iter()
+            .filter_map(|(child_of, aabb)| {
+                let (instance, interactable, occupants) =
+                    interactables.get(child_of.parent()).ok()?;
+                if interactable.mode != InteractionMode::Manual || !occupants.contains(player) {
+                    return None;
+                }
+                let distance = aabb.center().distance_squared(position);
+                Some((instance, interactable.priority, distance))
+            })
+            .max_by(|(_, p1, d1), (_, p2, d2)| p1.cmp(p2).then(d2.total_cmp(d1)))
+            .map(|(instance, _, _)| instance);
+
+        focus.set_if_neq(InteractionFocus(best));
+    }
+}
+
+/// Fires `Interacted` on the player's focused interactable for each interact request.
+pub fn handle_interact_requests(
+    mut commands: Commands,
+    mut requests: MessageReader<Interact>,
+    players: Query<&InteractionFocus>,
+    mut writer: MessageWriter<Interacted>,
+) {
+    for request in requests.read() {
+        let Ok(InteractionFocus(Some(target))) = players.get(request.player.entity()) else {
+            continue;
+        };
+
+        let event = Interacted {
+            entity: target.entity(),
+            actor: request.player.entity(),
+        };
+        writer.write(event);
+        commands.trigger(event);
+    }
+}
+
+/// Spawns the (hidden) interaction prompt.
+pub fn spawn_interaction_prompt(mut commands: Commands) {
+    commands.spawn((
+        Name::new("InteractionPrompt"),
+        InteractionPrompt,
+        Text2d::default(),
+        TextFont::from_font_size(8.0),
+        Visibility::Hidden,
+    ));
+}
+
+/// Shows the prompt above the local player's focused interactable.
+pub fn update_interaction_prompt(
+    players: Query<&InteractionFocus, With<LocalPlayer>>,
+    interactables: Query<(&Interactable, &Children)>,
+    sensors: Query<&ColliderAabb, With<InteractableSensor>>,
+    mut prompt: Single<(&mut Text2d, &mut Transform, &mut Visibility), With<InteractionPrompt>>,
+) {
+    let (text, transform, visibility) = &mut *prompt;
+
+    let target = players
+        .iter()
+        .find_map(|focus| focus.0)
+        .and_then(|target| interactables.get(target.entity()).ok())
+        .and_then(|(interactable, children)| {
+            let aabb = children.iter().find_map(|child| sensors.get(child).ok())?;
+            Some((interactable, aabb))
+        });
+
+    let Some((interactable, aabb)) = target else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    let label = format!("[E] {}", interactable.prompt);
+    if text.0 != label {
+        text.0 = label;
+    }
+    transform.translation = Vec3::new(aabb.center().x, aabb.max.y + PROMPT_MARGIN, PROMPT_Z);
+    visibility.set_if_neq(Visibility::Visible);
+}
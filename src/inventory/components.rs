@@ -0,0 +1,51 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// Kinds of items that can be carried.
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ItemKind {
+    #[default]
+    Log,
+    Stone,
+    Key,
+    Egg,
+    LargeEgg,
+    Milk,
+    LargeMilk,
+    Grass,
+}
+
+/// Items carried by an actor, by kind.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct Inventory(HashMap<ItemKind, u32>);
+
+impl Inventory {
+    /// How many items of `kind` are carried.
+    pub fn count(&self, kind: ItemKind) -> u32 {
+        self.0.get(&kind).copied().unwrap_or(0)
+    }
+
+    pub fn contains(&self, kind: ItemKind) -> bool {
+        self.count(kind) > 0
+    }
+
+    pub fn add(&mut self, kind: ItemKind, amount: u32) {
+        *self.0.entry(kind).or_default() += amount;
+    }
+
+    /// Removes `amount` items of `kind`. Returns false (and removes nothing)
+    /// if not enough are carried.
+    pub fn remove(&mut self, kind: ItemKind, amount: u32) -> bool {
+        let count = self.count(kind);
+        if count < amount {
+            return false;
+        }
+
+        if count == amount {
+            self.0.remove(&kind);
+        } else {
+            self.0.insert(kind, count - amount);
+        }
+        true
+    }
+}
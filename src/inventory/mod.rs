@@ -0,0 +1,5 @@
+pub mod components;
+mod plugin;
+
+pub use components::{Inventory, ItemKind};
+pub use plugin::InventoryPlugin;
@@ -0,0 +1,10 @@
+use super::components::{Inventory, ItemKind};
+use bevy::prelude::*;
+
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ItemKind>().register_type::<Inventory>();
+    }
+}
@@ -1,32 +1,46 @@
 use bevy::{prelude::*, window::WindowResolution};
 
 mod animals;
+mod behaviour;
+mod benchmark;
+mod building;
 mod camera;
+mod clock;
 mod core;
 #[cfg(feature = "debug")]
 mod debug;
 mod door;
 mod interactable;
+mod inventory;
 mod navigation;
+mod net;
 mod npcs;
 mod objects;
 mod physics;
 mod player;
 mod shaders;
+mod spatial;
 mod tiled;
 mod tools;
 
 use animals::AnimalsPlugin;
+use behaviour::BehaviourPlugin;
+use benchmark::BenchmarkPlugin;
+use building::BuildingPlugin;
 use camera::CameraPlugin;
+use clock::ClockPlugin;
 use core::CorePlugin;
 use door::DoorPlugin;
 use interactable::InteractablePlugin;
+use inventory::InventoryPlugin;
 use navigation::NavigationPlugin;
+use net::NetPlugin;
 use npcs::NpcsPlugin;
 use objects::ObjectsPlugin;
 use physics::PhysicsPlugin;
 use player::PlayerPlugin;
 use shaders::ShadersPlugin;
+use spatial::SpatialPlugin;
 use tiled::TiledPlugin;
 use tools::ToolsPlugin;
 
@@ -58,6 +72,15 @@ fn main() -> AppExit {
         NavigationPlugin,
         NpcsPlugin,
         AnimalsPlugin,
+        NetPlugin,
+    ));
+    app.add_plugins((
+        ClockPlugin,
+        InventoryPlugin,
+        BehaviourPlugin,
+        SpatialPlugin,
+        BenchmarkPlugin,
+        BuildingPlugin,
     ));
 
     #[cfg(feature = "debug")]
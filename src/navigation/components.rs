@@ -1,65 +1,67 @@
+use crate::physics::NonSolidTiledObject;
 use bevy::prelude::*;
 use moonshine_kind::Instance;
 use rand::Rng;
+use vleue_navigator::prelude::NavMesh;
 
 /// Navigation region defined by a polygon. Spawned from Tiled.
 /// Vertices are populated from Tiled polygon data after spawn.
 /// A NavMesh is automatically created from this region.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component, Default)]
+#[require(NonSolidTiledObject)]
 pub struct NavigationRegion {
     /// Polygon vertices in world space (populated from Tiled).
     #[reflect(ignore)]
     pub vertices: Vec<Vec2>,
+    /// Walkable area and zones random points are picked from
+    /// (updated when the navmesh is built).
+    #[reflect(ignore)]
+    pub sampler: RegionSampler,
 }
 
 impl NavigationRegion {
     /// Margin from polygon edges (should match navmesh agent_radius).
     const EDGE_MARGIN: f32 = 5.0;
+    /// Samples tried before giving up on finding a random point.
+    const SAMPLE_ATTEMPTS: usize = 64;
 
-    /// Get a random point inside the polygon using rejection sampling.
-    /// Points are guaranteed to be at least EDGE_MARGIN away from edges.
-    pub fn random_point(&self) -> Vec2 {
-        if self.vertices.len() < 3 {
-            return Vec2::ZERO;
-        }
-
+    /// Get a random point of the region, uniformly distributed over its
+    /// navmesh and weighted by `NavigationZone`s. Points in exclusion zones
+    /// are never returned. Until the navmesh is built, the polygon is
+    /// sampled instead, at least EDGE_MARGIN away from edges.
+    pub fn random_point(&self) -> Option<Vec2> {
         let mut rng = rand::rng();
 
-        // Bounding box rejection sampling
-        let min_x = self
-            .vertices
-            .iter()
-            .map(|v| v.x)
-            .fold(f32::INFINITY, f32::min);
-        let max_x = self
-            .vertices
-            .iter()
-            .map(|v| v.x)
-            .fold(f32::NEG_INFINITY, f32::max);
-        let min_y = self
-            .vertices
-            .iter()
-            .map(|v| v.y)
-            .fold(f32::INFINITY, f32::min);
-        let max_y = self
-            .vertices
-            .iter()
-            .map(|v| v.y)
-            .fold(f32::NEG_INFINITY, f32::max);
+        for _ in 0..Self::SAMPLE_ATTEMPTS {
+            let point = match self.sampler.sample_navmesh(&mut rng) {
+                Some(point) => point,
+                None => self.sample_outline(&mut rng)?,
+            };
+            if self.sampler.accepts(point, &mut rng) {
+                return Some(point);
+            }
+        }
+        None
+    }
 
+    /// Uniform point inside the polygon using bounding box rejection sampling.
+    fn sample_outline(&self, rng: &mut impl Rng) -> Option<Vec2> {
+        if self.vertices.len() < 3 {
+            return None;
+        }
+
+        let (min, max) = bounds(&self.vertices);
         for _ in 0..100 {
             let point = Vec2::new(
-                rng.random_range(min_x..max_x),
-                rng.random_range(min_y..max_y),
+                rng.random_range(min.x..max.x),
+                rng.random_range(min.y..max.y),
             );
             if self.contains(point) && self.distance_to_edge(point) >= Self::EDGE_MARGIN {
-                return point;
+                return Some(point);
             }
         }
-
-        // Fallback to centroid
-        self.vertices.iter().copied().sum::<Vec2>() / self.vertices.len() as f32
+        None
     }
 
     /// Calculate minimum distance from point to any polygon edge.
@@ -77,33 +79,179 @@ impl NavigationRegion {
         min_dist
     }
 
-    /// Check if a point is inside the polygon (ray casting algorithm).
+    /// Check if a point is inside the polygon.
     pub fn contains(&self, point: Vec2) -> bool {
-        let n = self.vertices.len();
-        if n < 3 {
-            return false;
+        polygon_contains(&self.vertices, point)
+    }
+}
+
+/// Area of a navigation region that random points favour or avoid.
+/// Spawned from Tiled as a polygon (e.g. crop fields and water with weight 0,
+/// grass near the trough with weight 3).
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+#[require(NonSolidTiledObject)]
+pub struct NavigationZone {
+    /// How likely points in the zone are compared to elsewhere (1).
+    /// 0 excludes the zone.
+    pub weight: f32,
+    /// Polygon vertices in world space (populated from Tiled).
+    #[reflect(ignore)]
+    pub vertices: Vec<Vec2>,
+}
+
+impl Default for NavigationZone {
+    fn default() -> Self {
+        Self {
+            weight: 0.0,
+            vertices: Vec::new(),
         }
+    }
+}
 
-        let mut inside = false;
-        let mut j = n - 1;
+/// Triangulated navmesh of a region, for uniform sampling, and the zones
+/// weighting it.
+#[derive(Clone, Default)]
+pub struct RegionSampler {
+    triangles: Vec<[Vec2; 3]>,
+    /// Running total of triangle areas, to pick triangles by area.
+    cumulative_areas: Vec<f32>,
+    zones: Vec<(Vec<Vec2>, f32)>,
+}
 
-        for i in 0..n {
-            let vi = self.vertices[i];
-            let vj = self.vertices[j];
+impl RegionSampler {
+    /// Triangulates the polygons of a navmesh.
+    pub fn from_navmesh(navmesh: &NavMesh) -> Self {
+        let mesh = navmesh.get();
+        let mut triangles = Vec::new();
+        for layer in &mesh.layers {
+            for polygon in &layer.polygons {
+                let corners: Vec<Vec2> = polygon
+                    .vertices
+                    .iter()
+                    .filter_map(|&i| layer.vertices.get(i as usize))
+                    .map(|vertex| vertex.coords + layer.offset)
+                    .collect();
+                // Navmesh polygons are convex: fan out from the first corner
+                for i in 1..corners.len().saturating_sub(1) {
+                    triangles.push([corners[0], corners[i], corners[i + 1]]);
+                }
+            }
+        }
+
+        let mut total = 0.0;
+        let cumulative_areas = triangles
+            .iter()
+            .map(|[a, b, c]| {
+                total += (*b - *a).perp_dot(*c - *a).abs() / 2.0;
+                total
+            })
+            .collect();
+
+        Self {
+            triangles,
+            cumulative_areas,
+            zones: Vec::new(),
+        }
+    }
 
-            if ((vi.y > point.y) != (vj.y > point.y))
-                && (point.x < (vj.x - vi.x) * (point.y - vi.y) / (vj.y - vi.y) + vi.x)
-            {
-                inside = !inside;
+    /// Replaces the zones weighting the samples.
+    pub fn set_zones(&mut self, zones: Vec<(Vec<Vec2>, f32)>) {
+        self.zones = zones;
+    }
+
+    /// Uniform point on the navmesh, if it was triangulated.
+    fn sample_navmesh(&self, rng: &mut impl Rng) -> Option<Vec2> {
+        let total = *self.cumulative_areas.last()?;
+        if total <= 0.0 {
+            return None;
+        }
+
+        let target = rng.random_range(0.0..total);
+        let index = self
+            .cumulative_areas
+            .partition_point(|&area| area <= target)
+            .min(self.triangles.len() - 1);
+        let [a, b, c] = self.triangles[index];
+
+        // Uniform barycentric coordinates, folded back into the triangle
+        let (mut u, mut v) = (rng.random::<f32>(), rng.random::<f32>());
+        if u + v > 1.0 {
+            (u, v) = (1.0 - u, 1.0 - v);
+        }
+        Some(a + (b - a) * u + (c - a) * v)
+    }
+
+    /// Weight of a point: the highest of the zones containing it (1 outside
+    /// any), except that exclusion zones always win.
+    fn weight_at(&self, point: Vec2) -> f32 {
+        let mut weight: Option<f32> = None;
+        for (vertices, zone_weight) in &self.zones {
+            if !polygon_contains(vertices, point) {
+                continue;
             }
-            j = i;
+            if *zone_weight <= 0.0 {
+                return 0.0;
+            }
+            weight = Some(weight.map_or(*zone_weight, |weight| weight.max(*zone_weight)));
         }
+        weight.unwrap_or(1.0)
+    }
 
-        inside
+    /// Rejection step turning uniform samples into weighted ones.
+    fn accepts(&self, point: Vec2, rng: &mut impl Rng) -> bool {
+        let max_weight = self
+            .zones
+            .iter()
+            .map(|(_, weight)| *weight)
+            .fold(1.0, f32::max);
+        rng.random::<f32>() * max_weight < self.weight_at(point)
     }
 }
 
-/// Reference to the navigation region this NPC belongs to.
+/// Walkable connection between two navigation regions (door, bridge, gate).
+/// Spawned from Tiled as a polyline whose first point lies in one region and
+/// last point in the other; NPCs walk straight from one end to the other.
+/// Disable it at runtime (closed door, raised gate) to take it off routes.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+#[require(NonSolidTiledObject)]
+pub struct NavigationLink {
+    pub enabled: bool,
+    /// Door the link goes through (object reference in Tiled, same map).
+    /// The link follows the door: disabled while NPCs can't get through it.
+    pub door: Option<Entity>,
+    /// First point in world space (populated from Tiled).
+    #[reflect(ignore)]
+    pub start: Vec2,
+    /// Last point in world space (populated from Tiled).
+    #[reflect(ignore)]
+    pub end: Vec2,
+}
+
+impl Default for NavigationLink {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            door: None,
+            start: Vec2::ZERO,
+            end: Vec2::ZERO,
+        }
+    }
+}
+
+impl NavigationLink {
+    /// Whether a path walks across this link, in either direction.
+    pub fn is_crossed_by(&self, waypoints: &[Vec2]) -> bool {
+        waypoints.windows(2).any(|step| {
+            (step[0] == self.start && step[1] == self.end)
+                || (step[0] == self.end && step[1] == self.start)
+        })
+    }
+}
+
+/// Reference to the navigation region this NPC is in.
+/// Updated when the NPC walks across a `NavigationLink` into another region.
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct InNavigationRegion(pub Instance<NavigationRegion>);
@@ -114,7 +262,18 @@ pub struct InNavigationRegion(pub Instance<NavigationRegion>);
 #[derive(Component)]
 pub struct NavMeshRef(pub Entity);
 
+/// NavMesh covering a whole map, for players walking to where they click.
+/// Unlike region navmeshes, players don't carve it.
+#[derive(Component)]
+pub struct PlayerNavMesh {
+    /// Area covered, in world space.
+    pub bounds: Rect,
+}
+
 /// Marker for obstacles that carve out the navmesh.
+/// Static obstacles (trees, rocks, walls) also get `CachableObstacle`: they are
+/// carved once into a cached base that rebuilds for moving ones reuse, and
+/// spawning or despawning one rebuilds it.
 #[derive(Component)]
 pub struct NavMeshObstacle;
 
@@ -166,3 +325,36 @@ fn point_to_segment_distance(point: Vec2, a: Vec2, b: Vec2) -> f32 {
     let closest = a + ab * t;
     point.distance(closest)
 }
+
+/// Axis-aligned bounds of a set of points.
+fn bounds(points: &[Vec2]) -> (Vec2, Vec2) {
+    points.iter().fold(
+        (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+        |(min, max), point| (min.min(*point), max.max(*point)),
+    )
+}
+
+/// Check if a point is inside a polygon (ray casting algorithm).
+fn polygon_contains(vertices: &[Vec2], point: Vec2) -> bool {
+    let n = vertices.len();
+    if n < 3 {
+        return false;
+    }
+
+    let mut inside = false;
+    let mut j = n - 1;
+
+    for i in 0..n {
+        let vi = vertices[i];
+        let vj = vertices[j];
+
+        if ((vi.y > point.y) != (vj.y > point.y))
+            && (point.x < (vj.x - vi.x) * (point.y - vi.y) / (vj.y - vi.y) + vi.x)
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+
+    inside
+}
@@ -1,5 +1,7 @@
 pub mod components;
+mod pathfinder;
 mod plugin;
 mod systems;
 
+pub use pathfinder::Pathfinder;
 pub use plugin::NavigationPlugin;
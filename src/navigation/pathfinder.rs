@@ -0,0 +1,215 @@
+use super::components::{NavMeshRef, NavigationLink, NavigationRegion, PlayerNavMesh};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use moonshine_kind::Instance;
+use vleue_navigator::prelude::*;
+
+/// Plans paths over navigation regions. Each region has its own navmesh;
+/// routes to other regions go through enabled `NavigationLink`s, stitching
+/// the per-navmesh paths together. Players path over whole map navmeshes.
+#[derive(SystemParam)]
+pub struct Pathfinder<'w, 's> {
+    regions: Query<
+        'w,
+        's,
+        (
+            Instance<NavigationRegion>,
+            &'static NavigationRegion,
+            &'static NavMeshRef,
+        ),
+    >,
+    links: Query<'w, 's, &'static NavigationLink>,
+    player_navmeshes: Query<'w, 's, (&'static PlayerNavMesh, Entity)>,
+    navmesh_query: Query<'w, 's, (&'static NavMeshStatus, &'static ManagedNavMesh)>,
+    navmeshes: Res<'w, Assets<NavMesh>>,
+}
+
+/// One way across a link.
+struct Crossing {
+    from: Instance<NavigationRegion>,
+    entry: Vec2,
+    to: Instance<NavigationRegion>,
+    exit: Vec2,
+}
+
+impl Pathfinder<'_, '_> {
+    /// A region and its navmesh, if the navmesh is built.
+    pub fn get(&self, region: Instance<NavigationRegion>) -> Option<(&NavigationRegion, &NavMesh)> {
+        let (_, region, navmesh_ref) = self.regions.get(region.entity()).ok()?;
+        let (status, managed) = self.navmesh_query.get(navmesh_ref.0).ok()?;
+        if *status != NavMeshStatus::Built {
+            return None;
+        }
+        Some((region, self.navmeshes.get(&**managed)?))
+    }
+
+    /// Path for a player from `start` to `goal`, on the largest map containing
+    /// both. Waypoints exclude `start` and end at `goal`.
+    pub fn player_path(&self, start: Vec2, goal: Vec2) -> Option<Vec<Vec2>> {
+        let (_, entity) = self
+            .player_navmeshes
+            .iter()
+            .filter(|(navmesh, _)| navmesh.bounds.contains(start) && navmesh.bounds.contains(goal))
+            .max_by(|(a, _), (b, _)| {
+                a.bounds
+                    .size()
+                    .element_product()
+                    .total_cmp(&b.bounds.size().element_product())
+            })?;
+        let (status, managed) = self.navmesh_query.get(entity).ok()?;
+        if *status != NavMeshStatus::Built {
+            return None;
+        }
+        let navmesh = self.navmeshes.get(&**managed)?;
+        navmesh.path(start, goal).map(|path| path.path)
+    }
+
+    /// The region containing a point.
+    pub fn region_at(&self, point: Vec2) -> Option<Instance<NavigationRegion>> {
+        self.regions
+            .iter()
+            .find(|(_, region, _)| region.contains(point))
+            .map(|(instance, _, _)| instance)
+    }
+
+    /// Both ways across every enabled link joining two regions.
+    fn crossings(&self) -> Vec<Crossing> {
+        let mut crossings = Vec::new();
+        for link in self.links.iter().filter(|link| link.enabled) {
+            let (Some(a), Some(b)) = (self.region_at(link.start), self.region_at(link.end)) else {
+                continue;
+            };
+            if a == b {
+                continue;
+            }
+            crossings.push(Crossing {
+                from: a,
+                entry: link.start,
+                to: b,
+                exit: link.end,
+            });
+            crossings.push(Crossing {
+                from: b,
+                entry: link.end,
+                to: a,
+                exit: link.start,
+            });
+        }
+        crossings
+    }
+
+    /// Whether enabled links join two regions (without checking navmeshes).
+    pub fn connected(
+        &self,
+        from: Instance<NavigationRegion>,
+        to: Instance<NavigationRegion>,
+    ) -> bool {
+        let crossings = self.crossings();
+        let mut reached = vec![from];
+        let mut next = 0;
+        while let Some(&region) = reached.get(next) {
+            if region == to {
+                return true;
+            }
+            for crossing in crossings.iter().filter(|crossing| crossing.from == region) {
+                if !reached.contains(&crossing.to) {
+                    reached.push(crossing.to);
+                }
+            }
+            next += 1;
+        }
+        false
+    }
+
+    /// Shortest path from `start` in region `from` to `goal`, crossing into
+    /// other regions if needed. Waypoints exclude `start` and end at `goal`.
+    pub fn path(
+        &self,
+        from: Instance<NavigationRegion>,
+        start: Vec2,
+        goal: Vec2,
+    ) -> Option<Vec<Vec2>> {
+        let from_contains_goal = self
+            .regions
+            .get(from.entity())
+            .is_ok_and(|(_, region, _)| region.contains(goal));
+        let goal_region = if from_contains_goal {
+            from
+        } else {
+            self.region_at(goal)?
+        };
+        if goal_region == from {
+            let (_, navmesh) = self.get(from)?;
+            return navmesh.path(start, goal).map(|path| path.path);
+        }
+
+        // Dijkstra over link exits. Node 0 is the start, node i + 1 the
+        // exit of crossing i.
+        let crossings = self.crossings();
+        let count = crossings.len() + 1;
+        let node = |n: usize| match n {
+            0 => (from, start),
+            _ => (crossings[n - 1].to, crossings[n - 1].exit),
+        };
+        let mut cost = vec![f32::INFINITY; count];
+        let mut settled = vec![false; count];
+        // Previous node and waypoints from it, for each reached node
+        let mut legs: Vec<Option<(usize, Vec<Vec2>)>> = vec![None; count];
+        let mut best: Option<(f32, usize, Vec<Vec2>)> = None;
+        cost[0] = 0.0;
+
+        while let Some(current) = (0..count)
+            .filter(|&n| !settled[n] && cost[n].is_finite())
+            .min_by(|&a, &b| cost[a].total_cmp(&cost[b]))
+        {
+            if best
+                .as_ref()
+                .is_some_and(|(total, ..)| *total <= cost[current])
+            {
+                break;
+            }
+            settled[current] = true;
+
+            let (region, position) = node(current);
+            let Some((_, navmesh)) = self.get(region) else {
+                continue;
+            };
+
+            if region == goal_region
+                && let Some(path) = navmesh.path(position, goal)
+            {
+                let total = cost[current] + path.length;
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_total, ..)| total < *best_total)
+                {
+                    best = Some((total, current, path.path));
+                }
+            }
+
+            for (i, crossing) in crossings.iter().enumerate() {
+                if crossing.from != region || settled[i + 1] {
+                    continue;
+                }
+                let Some(path) = navmesh.path(position, crossing.entry) else {
+                    continue;
+                };
+                let total = cost[current] + path.length + crossing.entry.distance(crossing.exit);
+                if total < cost[i + 1] {
+                    cost[i + 1] = total;
+                    let mut waypoints = path.path;
+                    waypoints.push(crossing.exit);
+                    legs[i + 1] = Some((current, waypoints));
+                }
+            }
+        }
+
+        // Walk back from the goal, then stitch the legs in order
+        let (_, mut current, last_leg) = best?;
+        let mut stitched = vec![last_leg];
+        while let Some((previous, waypoints)) = legs[current].take() {
+            stitched.push(waypoints);
+            current = previous;
+        }
+        Some(stitched.into_iter().rev().flatten().collect())
+    }
+}
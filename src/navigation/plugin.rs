@@ -1,8 +1,12 @@
 use super::{
-    components::{InNavigationRegion, NavMeshObstacle, NavigationRegion},
+    components::{
+        InNavigationRegion, NavMeshObstacle, NavigationLink, NavigationRegion, NavigationZone,
+    },
     systems::{
-        draw_navigation_path_gizmo, link_npcs_to_navigation_regions,
-        populate_navigation_region_vertices,
+        draw_navigation_link_gizmo, draw_navigation_path_gizmo, ignore_player_obstacles,
+        link_npcs_to_navigation_regions, mark_tiled_obstacles, populate_navigation_link_ends,
+        populate_navigation_region_vertices, populate_navigation_zone_vertices,
+        spawn_player_navmesh, update_npc_navigation_regions, update_region_samplers,
     },
 };
 use avian2d::prelude::Collider;
@@ -22,12 +26,32 @@ impl Plugin for NavigationPlugin {
             ))
             // Register types
             .register_type::<NavigationRegion>()
+            .register_type::<NavigationLink>()
+            .register_type::<NavigationZone>()
             .register_type::<InNavigationRegion>()
             // Populate vertices and create NavMesh when NavigationRegion objects are created
             .add_observer(populate_navigation_region_vertices)
-            // Link NPCs to their navigation regions
-            .add_systems(Update, link_npcs_to_navigation_regions)
-            // Debug gizmos for paths
-            .add_systems(Update, draw_navigation_path_gizmo);
+            .add_observer(populate_navigation_link_ends)
+            .add_observer(populate_navigation_zone_vertices)
+            // Carve solid Tiled colliders out of the navmeshes
+            .add_systems(Update, mark_tiled_obstacles)
+            // Whole map navmeshes for click-to-move
+            .add_observer(spawn_player_navmesh)
+            .add_systems(Update, ignore_player_obstacles)
+            // Sample random points from the built navmeshes
+            .add_systems(Update, update_region_samplers)
+            // Link NPCs to their navigation regions, and follow them across links
+            .add_systems(
+                Update,
+                (
+                    link_npcs_to_navigation_regions,
+                    update_npc_navigation_regions,
+                ),
+            )
+            // Debug gizmos for paths and links
+            .add_systems(
+                Update,
+                (draw_navigation_path_gizmo, draw_navigation_link_gizmo),
+            );
     }
 }
@@ -1,6 +1,10 @@
-use super::components::{InNavigationRegion, NavMeshRef, NavigationPath, NavigationRegion};
-use crate::npcs::components::Npc;
-use avian2d::prelude::PhysicsGizmos;
+use super::components::NavMeshObstacle;
+use super::components::{
+    InNavigationRegion, NavMeshRef, NavigationLink, NavigationPath, NavigationRegion,
+    NavigationZone, PlayerNavMesh, RegionSampler,
+};
+use crate::{door::Door, npcs::components::Npc, physics::NonSolidTiledObject, player::Player};
+use avian2d::prelude::{PhysicsGizmos, Sensor};
 use bevy::{color::palettes, prelude::*};
 use bevy_ecs_tiled::prelude::*;
 use moonshine_kind::Instance;
@@ -47,6 +51,8 @@ pub fn populate_navigation_region_vertices(
                 fixed: Triangulation::from_outer_edges(&region.vertices),
                 agent_radius: 3.0,
                 simplify: 1.0,
+                // Let NPCs pushed against an obstacle still find a path out
+                default_search_delta: 1.0,
                 ..default()
             },
             NavMeshUpdateMode::Direct,
@@ -57,6 +63,183 @@ pub fn populate_navigation_region_vertices(
     commands.entity(entity).insert(NavMeshRef(navmesh_entity));
 }
 
+/// Spawns a navmesh over each map for click-to-move.
+pub fn spawn_player_navmesh(
+    trigger: On<TiledEvent<MapCreated>>,
+    mut commands: Commands,
+    maps: Query<(&TilemapAnchor, &Transform)>,
+    map_assets: Res<Assets<TiledMapAsset>>,
+) {
+    let event = trigger.event();
+    let Ok((anchor, transform)) = maps.get(event.origin) else {
+        return;
+    };
+    let Some(map) = event.get_map_asset(&map_assets) else {
+        return;
+    };
+
+    let origin = transform.translation.truncate();
+    let bounds = Rect::from_corners(
+        origin + map.world_space_from_tiled_position(anchor, Vec2::ZERO),
+        origin + map.world_space_from_tiled_position(anchor, map.rect.size()),
+    );
+
+    commands.spawn((
+        Name::new("PlayerNavMesh"),
+        PlayerNavMesh { bounds },
+        NavMeshSettings {
+            fixed: Triangulation::from_outer_edges(&[
+                bounds.min,
+                Vec2::new(bounds.max.x, bounds.min.y),
+                bounds.max,
+                Vec2::new(bounds.min.x, bounds.max.y),
+            ]),
+            agent_radius: 4.0,
+            simplify: 1.0,
+            default_search_delta: 1.0,
+            ..default()
+        },
+        // Players moving trigger rebuilds, even though it ignores them
+        NavMeshUpdateMode::Debounced(0.5),
+    ));
+}
+
+/// Keeps player colliders out of the player navmeshes, so a player's path
+/// doesn't start inside its own obstacle.
+pub fn ignore_player_obstacles(
+    mut navmeshes: Query<&mut NavMeshSettings, With<PlayerNavMesh>>,
+    new_navmeshes: Query<(), Added<PlayerNavMesh>>,
+    obstacles: Query<(Entity, &ChildOf), With<NavMeshObstacle>>,
+    new_obstacles: Query<(), Added<NavMeshObstacle>>,
+    players: Query<(), With<Player>>,
+) {
+    if new_navmeshes.is_empty() && new_obstacles.is_empty() {
+        return;
+    }
+
+    let ignored: Vec<Entity> = obstacles
+        .iter()
+        .filter(|(_, child_of)| players.contains(child_of.parent()))
+        .map(|(entity, _)| entity)
+        .collect();
+    for mut settings in &mut navmeshes {
+        if ignored
+            .iter()
+            .any(|entity| !settings.ignore_obstacles.contains(entity))
+        {
+            settings.ignore_obstacles.extend(ignored.iter().copied());
+        }
+    }
+}
+
+/// Populates NavigationLink ends from Tiled polyline data.
+pub fn populate_navigation_link_ends(
+    trigger: On<TiledEvent<ObjectCreated>>,
+    mut links: Query<(&Transform, &mut NavigationLink)>,
+    map_assets: Res<Assets<TiledMapAsset>>,
+) {
+    let event = trigger.event();
+
+    let Ok((transform, mut link)) = links.get_mut(event.origin) else {
+        return;
+    };
+    let Some(object) = event.get_object(&map_assets) else {
+        return;
+    };
+
+    let tiled::ObjectShape::Polyline { points } = &object.shape else {
+        warn!("NavigationLink object is not a polyline");
+        return;
+    };
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return;
+    };
+
+    let origin = transform.translation.truncate();
+    link.start = origin + Vec2::new(first.0, -first.1);
+    link.end = origin + Vec2::new(last.0, -last.1);
+}
+
+/// Populates NavigationZone vertices from Tiled polygon data.
+pub fn populate_navigation_zone_vertices(
+    trigger: On<TiledEvent<ObjectCreated>>,
+    mut zones: Query<(&Transform, &mut NavigationZone)>,
+    map_assets: Res<Assets<TiledMapAsset>>,
+) {
+    let event = trigger.event();
+
+    let Ok((transform, mut zone)) = zones.get_mut(event.origin) else {
+        return;
+    };
+    let Some(object) = event.get_object(&map_assets) else {
+        return;
+    };
+
+    let tiled::ObjectShape::Polygon { points } = &object.shape else {
+        warn!("NavigationZone object is not a polygon");
+        return;
+    };
+
+    let origin = transform.translation.truncate();
+    zone.vertices = points
+        .iter()
+        .map(|(x, y)| origin + Vec2::new(*x, -*y))
+        .collect();
+}
+
+/// Refreshes what regions sample random points from when a navmesh is
+/// (re)built or zones change. Regions keep their previous navmesh
+/// triangles while it rebuilds.
+pub fn update_region_samplers(
+    mut regions: Query<(&mut NavigationRegion, &NavMeshRef)>,
+    navmesh_query: Query<(Ref<NavMeshStatus>, &ManagedNavMesh)>,
+    navmeshes: Res<Assets<NavMesh>>,
+    zones: Query<Ref<NavigationZone>>,
+) {
+    let zones_changed = zones.iter().any(|zone| zone.is_changed());
+
+    for (mut region, navmesh_ref) in &mut regions {
+        let Ok((status, managed)) = navmesh_query.get(navmesh_ref.0) else {
+            continue;
+        };
+        let rebuilt = status.is_changed() && *status == NavMeshStatus::Built;
+        if !rebuilt && !zones_changed {
+            continue;
+        }
+
+        let mut sampler = match navmeshes.get(&**managed) {
+            Some(navmesh) if rebuilt => RegionSampler::from_navmesh(navmesh),
+            _ => region.sampler.clone(),
+        };
+        sampler.set_zones(
+            zones
+                .iter()
+                .filter(|zone| zone.vertices.len() >= 3)
+                .map(|zone| (zone.vertices.clone(), zone.weight))
+                .collect(),
+        );
+        region.sampler = sampler;
+    }
+}
+
+/// Makes solid Tiled colliders (walls, fences, trees placed as tiles) carve
+/// the navmesh. Doors stay passable (they open for NPCs), as do NPCs
+/// themselves and non-solid objects.
+pub fn mark_tiled_obstacles(
+    mut commands: Commands,
+    colliders: Query<(Entity, &TiledColliderOf), (Added<TiledColliderOf>, Without<Sensor>)>,
+    passable: Query<(), Or<(With<Npc>, With<Door>, With<NonSolidTiledObject>)>>,
+) {
+    for (collider, collider_of) in &colliders {
+        if passable.contains(collider_of.0) {
+            continue;
+        }
+        commands
+            .entity(collider)
+            .insert((NavMeshObstacle, CachableObstacle));
+    }
+}
+
 /// Links NPCs to their navigation region based on spawn position.
 pub fn link_npcs_to_navigation_regions(
     mut commands: Commands,
@@ -84,6 +267,29 @@ pub fn link_npcs_to_navigation_regions(
     }
 }
 
+/// Moves NPCs that walked out of their navigation region (across a link)
+/// into the region they are now in.
+pub fn update_npc_navigation_regions(
+    mut npcs: Query<(&Transform, &mut InNavigationRegion)>,
+    regions: Query<(Instance<NavigationRegion>, &NavigationRegion)>,
+) {
+    for (transform, mut in_region) in &mut npcs {
+        let pos = transform.translation.truncate();
+        if regions
+            .get(in_region.0.entity())
+            .is_ok_and(|(_, region)| region.contains(pos))
+        {
+            continue;
+        }
+
+        // Between regions (on a link): keep the old one until the NPC arrives
+        if let Some((region_instance, _)) = regions.iter().find(|(_, region)| region.contains(pos))
+        {
+            in_region.0 = region_instance;
+        }
+    }
+}
+
 /// Draws the navigation path as a gizmo line (only when PhysicsGizmos enabled).
 pub fn draw_navigation_path_gizmo(
     mut gizmos: Gizmos,
@@ -115,3 +321,25 @@ pub fn draw_navigation_path_gizmo(
         );
     }
 }
+
+/// Draws navigation links, enabled in green and disabled in red
+/// (only when PhysicsGizmos enabled).
+pub fn draw_navigation_link_gizmo(
+    mut gizmos: Gizmos,
+    links: Query<&NavigationLink>,
+    config_store: Res<GizmoConfigStore>,
+) {
+    let (config, _) = config_store.config::<PhysicsGizmos>();
+    if !config.enabled {
+        return;
+    }
+
+    for link in &links {
+        let color = if link.enabled {
+            palettes::tailwind::GREEN_400
+        } else {
+            palettes::tailwind::RED_400
+        };
+        gizmos.line_2d(link.start, link.end, color);
+    }
+}
@@ -0,0 +1,102 @@
+use super::protocol::InputState;
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Network identity shared by the host and clients for a replicated entity.
+///
+/// Map entities (NPCs, objects, doors, items placed in Tiled) derive their id from
+/// their spawn position, which is identical on every instance loading the same map.
+/// Players and items spawned at runtime use ids with the high bit set.
+#[derive(Component, Reflect, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[reflect(Component)]
+pub struct NetId(pub u32);
+
+impl NetId {
+    const DYNAMIC_BIT: u32 = 1 << 31;
+    /// Set on runtime ids of items, so they never clash with players.
+    const ITEM_BIT: u32 = 1 << 30;
+
+    /// Id for the `index`-th player joining a session (host is 0).
+    pub fn player(index: u32) -> Self {
+        Self(Self::DYNAMIC_BIT | index)
+    }
+
+    /// Id for the `index`-th item spawned during a session (drops, produce).
+    pub fn item(index: u32) -> Self {
+        Self(Self::DYNAMIC_BIT | Self::ITEM_BIT | index)
+    }
+
+    /// Whether the id was given at runtime rather than derived from the map.
+    pub fn is_dynamic(&self) -> bool {
+        self.0 & Self::DYNAMIC_BIT != 0
+    }
+
+    /// Deterministic id for a map entity of a given kind at its spawn position.
+    pub fn from_spawn(kind: u8, position: Vec2) -> Self {
+        // FNV-1a over kind + position rounded to whole pixels
+        let mut hash: u32 = 0x811c_9dc5;
+        let bytes = [kind]
+            .into_iter()
+            .chain((position.x.round() as i32).to_le_bytes())
+            .chain((position.y.round() as i32).to_le_bytes());
+        for byte in bytes {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+        Self(hash & !Self::DYNAMIC_BIT)
+    }
+}
+
+/// Latest input received from a remote client (host only).
+#[derive(Component, Default)]
+pub struct RemoteInput {
+    pub state: InputState,
+    /// Action counter value already turned into a tool action.
+    pub handled_actions: u8,
+    /// Interaction counter value already turned into an interaction.
+    pub handled_interactions: u8,
+}
+
+/// Positions received from the host, interpolated for display (client only).
+/// Replaces physics transform easing, as the host drives the position.
+#[derive(Component, Default)]
+#[require(NoTransformEasing)]
+pub struct InterpolationBuffer {
+    /// (receive time in seconds, position), oldest first.
+    pub samples: VecDeque<(f64, Vec2)>,
+}
+
+impl InterpolationBuffer {
+    /// Samples older than this are dropped (seconds).
+    const MAX_AGE: f64 = 1.0;
+
+    pub fn push(&mut self, time: f64, position: Vec2) {
+        self.samples.push_back((time, position));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(t, _)| time - *t > Self::MAX_AGE)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Position at `time`, linearly interpolated between the surrounding samples.
+    /// Clamps to the oldest/newest sample outside the buffered range.
+    pub fn sample(&self, time: f64) -> Option<Vec2> {
+        let (first_time, first) = *self.samples.front()?;
+        if time <= first_time {
+            return Some(first);
+        }
+
+        for ((t0, p0), (t1, p1)) in self.samples.iter().zip(self.samples.iter().skip(1)) {
+            if time <= *t1 {
+                let t = ((time - t0) / (t1 - t0).max(f64::EPSILON)) as f32;
+                return Some(p0.lerp(*p1, t));
+            }
+        }
+
+        self.samples.back().map(|(_, p)| *p)
+    }
+}
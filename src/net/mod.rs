@@ -0,0 +1,8 @@
+pub mod components;
+mod plugin;
+mod protocol;
+mod resources;
+mod systems;
+
+pub use plugin::NetPlugin;
+pub use systems::has_authority;
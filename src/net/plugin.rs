@@ -0,0 +1,138 @@
+use super::{
+    components::NetId,
+    resources::{ClientSession, HostSession, NetMode, NetSocket},
+    systems::{
+        apply_remote_inputs, apply_snapshot, assign_item_net_ids, assign_local_player_net_id,
+        assign_map_net_ids, client_receive, drop_inactive_clients, host_receive,
+        host_send_snapshots, interpolate_replicated, send_bye, send_hello, send_input,
+    },
+};
+use crate::{
+    npcs::NpcSystemSet,
+    player::{PlayerSystemSet, systems::follow_auto_walk},
+};
+use bevy::prelude::*;
+use std::net::{SocketAddr, UdpSocket};
+
+/// Port used by `--host` when none is given.
+const DEFAULT_PORT: u16 = 7777;
+
+/// Local network co-op over UDP.
+///
+/// - `--host [port]`: run the authoritative simulation and accept clients.
+/// - `--join <addr:port>`: send inputs to a host and mirror its snapshots.
+///
+/// Without either flag the game runs offline.
+pub struct NetPlugin;
+
+/// Session requested on the command line.
+enum NetArgs {
+    Host(u16),
+    Join(SocketAddr),
+}
+
+impl NetArgs {
+    fn parse() -> Option<Self> {
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--host" => {
+                    let port = args.next().and_then(|p| p.parse().ok());
+                    return Some(Self::Host(port.unwrap_or(DEFAULT_PORT)));
+                }
+                "--join" => {
+                    let Some(addr) = args.next().and_then(|a| a.parse().ok()) else {
+                        error!("--join expects an address like 127.0.0.1:{DEFAULT_PORT}");
+                        return None;
+                    };
+                    return Some(Self::Join(addr));
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn bind(&self) -> std::io::Result<UdpSocket> {
+        let socket = match self {
+            Self::Host(port) => UdpSocket::bind(("0.0.0.0", *port))?,
+            Self::Join(addr) => {
+                let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+                socket.connect(addr)?;
+                socket
+            }
+        };
+        socket.set_nonblocking(true)?;
+        Ok(socket)
+    }
+}
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<NetMode>()
+            .register_type::<NetId>()
+            .init_resource::<NetMode>();
+
+        let Some(args) = NetArgs::parse() else {
+            return;
+        };
+
+        let socket = match args.bind() {
+            Ok(socket) => socket,
+            Err(e) => {
+                error!("Failed to open network socket, running offline: {e}");
+                return;
+            }
+        };
+
+        // Both sides identify map entities the same way
+        app.insert_resource(NetSocket(socket)).add_systems(
+            PostUpdate,
+            assign_map_net_ids.after(TransformSystems::Propagate),
+        );
+
+        match args {
+            NetArgs::Host(port) => {
+                info!("Hosting on port {port}");
+                app.insert_resource(NetMode::Host)
+                    .init_resource::<HostSession>()
+                    .add_systems(PreUpdate, host_receive)
+                    .add_systems(
+                        Update,
+                        (
+                            apply_remote_inputs.before(PlayerSystemSet::ToolActions),
+                            drop_inactive_clients,
+                        ),
+                    )
+                    .add_systems(
+                        FixedUpdate,
+                        host_send_snapshots
+                            .after(PlayerSystemSet::Movement)
+                            .after(NpcSystemSet::Movement),
+                    )
+                    .add_systems(
+                        PostUpdate,
+                        (assign_local_player_net_id, assign_item_net_ids)
+                            .after(TransformSystems::Propagate),
+                    );
+            }
+            NetArgs::Join(addr) => {
+                info!("Joining {addr}");
+                app.insert_resource(NetMode::Client)
+                    .init_resource::<ClientSession>()
+                    .add_systems(PreUpdate, (client_receive, apply_snapshot).chain())
+                    .add_systems(
+                        Update,
+                        (
+                            send_hello,
+                            send_input
+                                .after(PlayerSystemSet::Input)
+                                .after(follow_auto_walk),
+                            interpolate_replicated,
+                        ),
+                    )
+                    .add_systems(Last, send_bye);
+            }
+        }
+    }
+}
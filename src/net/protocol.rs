@@ -0,0 +1,623 @@
+use super::components::NetId;
+use crate::{
+    door::DoorState,
+    inventory::ItemKind,
+    player::components::{EquippedTool, PlayerAnimation},
+};
+use bevy::prelude::*;
+
+/// Magic number prefixed to every datagram so stray packets are ignored.
+const PROTOCOL_ID: u32 = 0x4352_4F50; // "CROP"
+
+/// Largest datagram we send or accept.
+pub const MAX_PACKET_SIZE: usize = 65_507;
+
+/// Packets sent from a client to the host.
+#[derive(Debug, PartialEq)]
+pub enum ClientPacket {
+    /// Join request, resent until a Welcome arrives.
+    Hello,
+    /// Latest input state of the client's player.
+    Input(InputState),
+    /// Client is leaving.
+    Bye,
+}
+
+/// Packets sent from the host to a client.
+#[derive(Debug, PartialEq)]
+pub enum HostPacket {
+    /// Join accepted; `player` is the id of the client's own player.
+    Welcome { player: NetId },
+    /// Authoritative world state.
+    Snapshot(Snapshot),
+}
+
+/// Input state sent by clients every tick (unreliable, latest wins).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InputState {
+    /// Increasing sequence number, used to drop reordered packets.
+    pub seq: u32,
+    /// Movement direction (not normalized).
+    pub direction: Vec2,
+    /// Tool the client has equipped.
+    pub equipped: EquippedTool,
+    /// Number of tool actions requested so far (wrapping).
+    /// A counter instead of a flag so a lost packet doesn't lose the press.
+    pub actions: u8,
+    /// Number of interact presses so far (wrapping).
+    pub interactions: u8,
+    /// World position the client aims tools at, if any.
+    pub aim: Option<Vec2>,
+}
+
+/// Authoritative world state at a given host tick.
+#[derive(Debug, Default, PartialEq)]
+pub struct Snapshot {
+    pub tick: u32,
+    /// In-game clock (day and minutes since midnight).
+    pub day: u32,
+    pub minutes: f32,
+    pub players: Vec<PlayerSnapshot>,
+    pub npcs: Vec<NpcSnapshot>,
+    pub objects: Vec<ObjectSnapshot>,
+    pub doors: Vec<DoorSnapshot>,
+    pub items: Vec<ItemSnapshot>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerSnapshot {
+    pub id: NetId,
+    pub position: Vec2,
+    pub animation: PlayerAnimation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NpcSnapshot {
+    pub id: NetId,
+    pub position: Vec2,
+    pub moving: bool,
+    pub flip_x: bool,
+    /// Hidden on the host (e.g. an animal sheltered in its home).
+    pub hidden: bool,
+    /// Running away from something (plays the run animation).
+    pub fleeing: bool,
+    /// Needs of an animal, shown in its emotes and interaction prompt.
+    pub needs: Option<NeedsSnapshot>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NeedsSnapshot {
+    pub hunger: f32,
+    pub happiness: f32,
+    /// Petted on the current day.
+    pub petted_today: bool,
+    /// Whether the animal can be milked today (None if it is never milked).
+    pub milkable: Option<bool>,
+}
+
+/// Damageable object (tree, rock). Objects missing from a snapshot were destroyed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObjectSnapshot {
+    pub id: NetId,
+    pub health: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DoorSnapshot {
+    pub id: NetId,
+    pub state: DoorState,
+    /// Someone just tried the door while it was locked.
+    pub rattling: bool,
+}
+
+/// Item lying in the world. Items missing from a snapshot were collected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ItemSnapshot {
+    pub id: NetId,
+    pub kind: ItemKind,
+    pub position: Vec3,
+}
+
+impl ClientPacket {
+    const HELLO: u8 = 0;
+    const INPUT: u8 = 1;
+    const BYE: u8 = 2;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        match self {
+            Self::Hello => w.u8(Self::HELLO),
+            Self::Input(input) => {
+                w.u8(Self::INPUT);
+                w.u32(input.seq);
+                w.vec2(input.direction);
+                w.u8(equipped_to_u8(input.equipped));
+                w.u8(input.actions);
+                w.u8(input.interactions);
+                match input.aim {
+                    Some(aim) => {
+                        w.u8(1);
+                        w.vec2(aim);
+                    }
+                    None => w.u8(0),
+                }
+            }
+            Self::Bye => w.u8(Self::BYE),
+        }
+        w.finish()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut r = Reader::new(bytes)?;
+        match r.u8()? {
+            Self::HELLO => Some(Self::Hello),
+            Self::INPUT => Some(Self::Input(InputState {
+                seq: r.u32()?,
+                direction: r.vec2()?,
+                equipped: equipped_from_u8(r.u8()?)?,
+                actions: r.u8()?,
+                interactions: r.u8()?,
+                aim: match r.u8()? {
+                    0 => None,
+                    _ => Some(r.vec2()?),
+                },
+            })),
+            Self::BYE => Some(Self::Bye),
+            _ => None,
+        }
+    }
+}
+
+impl HostPacket {
+    const WELCOME: u8 = 0;
+    const SNAPSHOT: u8 = 1;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        match self {
+            Self::Welcome { player } => {
+                w.u8(Self::WELCOME);
+                w.u32(player.0);
+            }
+            Self::Snapshot(snapshot) => {
+                w.u8(Self::SNAPSHOT);
+                w.u32(snapshot.tick);
+                w.u32(snapshot.day);
+                w.f32(snapshot.minutes);
+
+                w.u16(snapshot.players.len() as u16);
+                for player in &snapshot.players {
+                    w.u32(player.id.0);
+                    w.vec2(player.position);
+                    w.u8(player.animation as u8);
+                }
+
+                w.u16(snapshot.npcs.len() as u16);
+                for npc in &snapshot.npcs {
+                    w.u32(npc.id.0);
+                    w.vec2(npc.position);
+                    w.u8(npc.moving as u8
+                        | (npc.flip_x as u8) << 1
+                        | (npc.hidden as u8) << 2
+                        | (npc.fleeing as u8) << 3
+                        | (npc.needs.is_some() as u8) << 4);
+                    if let Some(needs) = npc.needs {
+                        w.f32(needs.hunger);
+                        w.f32(needs.happiness);
+                        w.u8(needs.petted_today as u8
+                            | (needs.milkable.is_some() as u8) << 1
+                            | ((needs.milkable == Some(true)) as u8) << 2);
+                    }
+                }
+
+                w.u16(snapshot.objects.len() as u16);
+                for object in &snapshot.objects {
+                    w.u32(object.id.0);
+                    w.f32(object.health);
+                }
+
+                w.u16(snapshot.doors.len() as u16);
+                for door in &snapshot.doors {
+                    w.u32(door.id.0);
+                    w.u8(door_state_to_u8(door.state));
+                    w.u8(door.rattling as u8);
+                }
+
+                w.u16(snapshot.items.len() as u16);
+                for item in &snapshot.items {
+                    w.u32(item.id.0);
+                    w.u8(item_kind_to_u8(item.kind));
+                    w.vec3(item.position);
+                }
+            }
+        }
+        w.finish()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut r = Reader::new(bytes)?;
+        match r.u8()? {
+            Self::WELCOME => Some(Self::Welcome {
+                player: NetId(r.u32()?),
+            }),
+            Self::SNAPSHOT => {
+                let mut snapshot = Snapshot {
+                    tick: r.u32()?,
+                    day: r.u32()?,
+                    minutes: r.f32()?,
+                    ..default()
+                };
+
+                for _ in 0..r.u16()? {
+                    snapshot.players.push(PlayerSnapshot {
+                        id: NetId(r.u32()?),
+                        position: r.vec2()?,
+                        animation: player_animation_from_u8(r.u8()?)?,
+                    });
+                }
+
+                for _ in 0..r.u16()? {
+                    let id = NetId(r.u32()?);
+                    let position = r.vec2()?;
+                    let flags = r.u8()?;
+                    snapshot.npcs.push(NpcSnapshot {
+                        id,
+                        position,
+                        moving: flags & 1 != 0,
+                        flip_x: flags & 2 != 0,
+                        hidden: flags & 4 != 0,
+                        fleeing: flags & 8 != 0,
+                        needs: if flags & 16 != 0 {
+                            let hunger = r.f32()?;
+                            let happiness = r.f32()?;
+                            let needs_flags = r.u8()?;
+                            Some(NeedsSnapshot {
+                                hunger,
+                                happiness,
+                                petted_today: needs_flags & 1 != 0,
+                                milkable: (needs_flags & 2 != 0).then_some(needs_flags & 4 != 0),
+                            })
+                        } else {
+                            None
+                        },
+                    });
+                }
+
+                for _ in 0..r.u16()? {
+                    snapshot.objects.push(ObjectSnapshot {
+                        id: NetId(r.u32()?),
+                        health: r.f32()?,
+                    });
+                }
+
+                for _ in 0..r.u16()? {
+                    snapshot.doors.push(DoorSnapshot {
+                        id: NetId(r.u32()?),
+                        state: door_state_from_u8(r.u8()?)?,
+                        rattling: r.u8()? != 0,
+                    });
+                }
+
+                for _ in 0..r.u16()? {
+                    snapshot.items.push(ItemSnapshot {
+                        id: NetId(r.u32()?),
+                        kind: item_kind_from_u8(r.u8()?)?,
+                        position: r.vec3()?,
+                    });
+                }
+
+                Some(Self::Snapshot(snapshot))
+            }
+            _ => None,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Enum encodings
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// PlayerAnimation variants in declaration order (index = wire value).
+const PLAYER_ANIMATIONS: [PlayerAnimation; 20] = [
+    PlayerAnimation::IdleFront,
+    PlayerAnimation::IdleBack,
+    PlayerAnimation::IdleLeft,
+    PlayerAnimation::IdleRight,
+    PlayerAnimation::WalkingFront,
+    PlayerAnimation::WalkingBack,
+    PlayerAnimation::WalkingLeft,
+    PlayerAnimation::WalkingRight,
+    PlayerAnimation::TilingFront,
+    PlayerAnimation::TilingBack,
+    PlayerAnimation::TilingLeft,
+    PlayerAnimation::TilingRight,
+    PlayerAnimation::ChoppingFront,
+    PlayerAnimation::ChoppingBack,
+    PlayerAnimation::ChoppingLeft,
+    PlayerAnimation::ChoppingRight,
+    PlayerAnimation::WateringFront,
+    PlayerAnimation::WateringBack,
+    PlayerAnimation::WateringLeft,
+    PlayerAnimation::WateringRight,
+];
+
+fn player_animation_from_u8(value: u8) -> Option<PlayerAnimation> {
+    PLAYER_ANIMATIONS.get(value as usize).copied()
+}
+
+fn equipped_to_u8(tool: EquippedTool) -> u8 {
+    match tool {
+        EquippedTool::None => 0,
+        EquippedTool::Axe => 1,
+        EquippedTool::Hoe => 2,
+        EquippedTool::WateringCan => 3,
+    }
+}
+
+fn equipped_from_u8(value: u8) -> Option<EquippedTool> {
+    match value {
+        0 => Some(EquippedTool::None),
+        1 => Some(EquippedTool::Axe),
+        2 => Some(EquippedTool::Hoe),
+        3 => Some(EquippedTool::WateringCan),
+        _ => None,
+    }
+}
+
+fn door_state_to_u8(state: DoorState) -> u8 {
+    match state {
+        DoorState::Closed => 0,
+        DoorState::Open => 1,
+        DoorState::Opening => 2,
+        DoorState::Closing => 3,
+        DoorState::Locked => 4,
+    }
+}
+
+fn door_state_from_u8(value: u8) -> Option<DoorState> {
+    match value {
+        0 => Some(DoorState::Closed),
+        1 => Some(DoorState::Open),
+        2 => Some(DoorState::Opening),
+        3 => Some(DoorState::Closing),
+        4 => Some(DoorState::Locked),
+        _ => None,
+    }
+}
+
+/// ItemKind variants in declaration order (index = wire value).
+const ITEM_KINDS: [ItemKind; 8] = [
+    ItemKind::Log,
+    ItemKind::Stone,
+    ItemKind::Key,
+    ItemKind::Egg,
+    ItemKind::LargeEgg,
+    ItemKind::Milk,
+    ItemKind::LargeMilk,
+    ItemKind::Grass,
+];
+
+fn item_kind_to_u8(kind: ItemKind) -> u8 {
+    ITEM_KINDS.iter().position(|k| *k == kind).unwrap_or(0) as u8
+}
+
+fn item_kind_from_u8(value: u8) -> Option<ItemKind> {
+    ITEM_KINDS.get(value as usize).copied()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Byte encoding (little endian)
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn new() -> Self {
+        let mut w = Self(Vec::with_capacity(64));
+        w.u32(PROTOCOL_ID);
+        w
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.0
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn vec2(&mut self, value: Vec2) {
+        self.f32(value.x);
+        self.f32(value.y);
+    }
+
+    fn vec3(&mut self, value: Vec3) {
+        self.vec2(value.truncate());
+        self.f32(value.z);
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    /// Returns None if the packet doesn't start with our protocol id.
+    fn new(bytes: &'a [u8]) -> Option<Self> {
+        let mut r = Self(bytes);
+        (r.u32()? == PROTOCOL_ID).then_some(r)
+    }
+
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, tail) = self.0.split_first_chunk::<N>()?;
+        self.0 = tail;
+        Some(*head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|[b]| b)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        self.take().map(f32::from_le_bytes)
+    }
+
+    fn vec2(&mut self) -> Option<Vec2> {
+        Some(Vec2::new(self.f32()?, self.f32()?))
+    }
+
+    fn vec3(&mut self) -> Option<Vec3> {
+        Some(self.vec2()?.extend(self.f32()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip_client(packet: ClientPacket) {
+        assert_eq!(ClientPacket::decode(&packet.encode()), Some(packet));
+    }
+
+    fn round_trip_host(packet: HostPacket) {
+        assert_eq!(HostPacket::decode(&packet.encode()), Some(packet));
+    }
+
+    #[test]
+    fn client_packets_round_trip() {
+        round_trip_client(ClientPacket::Hello);
+        round_trip_client(ClientPacket::Bye);
+        round_trip_client(ClientPacket::Input(InputState {
+            seq: 42,
+            direction: Vec2::new(-1.0, 1.0),
+            equipped: EquippedTool::WateringCan,
+            actions: 255,
+            interactions: 3,
+            aim: Some(Vec2::new(120.5, -33.25)),
+        }));
+        round_trip_client(ClientPacket::Input(InputState {
+            aim: None,
+            ..default()
+        }));
+    }
+
+    #[test]
+    fn welcome_round_trips() {
+        round_trip_host(HostPacket::Welcome {
+            player: NetId::player(2),
+        });
+    }
+
+    #[test]
+    fn snapshot_round_trips() {
+        let npc = NpcSnapshot {
+            id: NetId(7),
+            position: Vec2::new(10.0, 20.0),
+            moving: true,
+            flip_x: false,
+            hidden: true,
+            fleeing: false,
+            needs: None,
+        };
+        let needs = NeedsSnapshot {
+            hunger: 12.5,
+            happiness: 80.0,
+            petted_today: true,
+            milkable: None,
+        };
+
+        round_trip_host(HostPacket::Snapshot(Snapshot {
+            tick: 1000,
+            day: 3,
+            minutes: 615.5,
+            players: PLAYER_ANIMATIONS
+                .iter()
+                .enumerate()
+                .map(|(i, &animation)| PlayerSnapshot {
+                    id: NetId::player(i as u32),
+                    position: Vec2::new(i as f32, -1.5),
+                    animation,
+                })
+                .collect(),
+            npcs: vec![
+                npc,
+                NpcSnapshot {
+                    flip_x: true,
+                    fleeing: true,
+                    needs: Some(needs),
+                    ..npc
+                },
+                NpcSnapshot {
+                    needs: Some(NeedsSnapshot {
+                        milkable: Some(false),
+                        ..needs
+                    }),
+                    ..npc
+                },
+                NpcSnapshot {
+                    needs: Some(NeedsSnapshot {
+                        petted_today: false,
+                        milkable: Some(true),
+                        ..needs
+                    }),
+                    ..npc
+                },
+            ],
+            objects: vec![ObjectSnapshot {
+                id: NetId(9),
+                health: 2.0,
+            }],
+            doors: [
+                DoorState::Closed,
+                DoorState::Open,
+                DoorState::Opening,
+                DoorState::Closing,
+                DoorState::Locked,
+            ]
+            .into_iter()
+            .map(|state| DoorSnapshot {
+                id: NetId(11),
+                state,
+                rattling: state == DoorState::Locked,
+            })
+            .collect(),
+            items: ITEM_KINDS
+                .iter()
+                .enumerate()
+                .map(|(i, &kind)| ItemSnapshot {
+                    id: NetId::item(i as u32),
+                    kind,
+                    position: Vec3::new(4.0, 8.0, i as f32),
+                })
+                .collect(),
+        }));
+    }
+
+    #[test]
+    fn rejects_foreign_and_truncated_packets() {
+        let mut hello = ClientPacket::Hello.encode();
+        hello[0] ^= 0xFF;
+        assert_eq!(ClientPacket::decode(&hello), None);
+
+        let input = ClientPacket::Input(InputState::default()).encode();
+        assert_eq!(ClientPacket::decode(&input[..input.len() - 1]), None);
+    }
+}
@@ -0,0 +1,59 @@
+use super::{components::NetId, protocol::Snapshot};
+use crate::player::Player;
+use bevy::{platform::collections::HashMap, prelude::*};
+use moonshine_kind::Instance;
+use std::net::{SocketAddr, UdpSocket};
+
+/// Role of this game instance in a network session.
+#[derive(Resource, Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Resource)]
+pub enum NetMode {
+    /// Single player, no networking.
+    #[default]
+    Offline,
+    /// Runs the authoritative simulation and accepts clients.
+    Host,
+    /// Sends inputs to a host and displays its snapshots.
+    Client,
+}
+
+/// Non-blocking UDP socket for the session.
+/// Clients `connect` it to the host address.
+#[derive(Resource)]
+pub struct NetSocket(pub UdpSocket);
+
+/// A client connected to the host.
+pub struct RemoteClient {
+    pub player: Instance<Player>,
+    pub id: NetId,
+    /// Elapsed time of the last packet received from this client.
+    pub last_seen: f64,
+}
+
+/// Host-side session state.
+#[derive(Resource, Default)]
+pub struct HostSession {
+    pub clients: HashMap<SocketAddr, RemoteClient>,
+    /// Index for the next joining player (0 is the host's own player).
+    pub next_player: u32,
+    /// Index for the next item spawned at runtime.
+    pub next_item: u32,
+    /// Fixed ticks simulated so far.
+    pub tick: u32,
+}
+
+/// Client-side session state.
+#[derive(Resource, Default)]
+pub struct ClientSession {
+    /// Our own player's id, known once the host sent a Welcome.
+    pub local_player: Option<NetId>,
+    /// Tick of the newest snapshot applied (older ones are dropped).
+    pub last_tick: Option<u32>,
+    /// Newest snapshot received this frame, waiting to be applied.
+    pub pending: Option<Snapshot>,
+    pub input_seq: u32,
+    pub actions: u8,
+    pub interactions: u8,
+    /// Elapsed time of the last Hello sent (resent until welcomed).
+    pub last_hello: f64,
+}
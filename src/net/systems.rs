@@ -0,0 +1,620 @@
+use super::{
+    components::{InterpolationBuffer, NetId, RemoteInput},
+    protocol::{
+        ClientPacket, DoorSnapshot, HostPacket, InputState, ItemSnapshot, MAX_PACKET_SIZE,
+        NeedsSnapshot, NpcSnapshot, ObjectSnapshot, PlayerSnapshot, Snapshot,
+    },
+    resources::{ClientSession, HostSession, NetMode, NetSocket, RemoteClient},
+};
+use crate::{
+    animals::components::{Egg, Grass, Happiness, Hunger, Milk, Milkable},
+    clock::GameClock,
+    core::components::{Fleeing, Health, Moving},
+    door::{Door, DoorRattle, DoorState},
+    inventory::ItemKind,
+    npcs::components::Npc,
+    objects::components::{Item, Key, Log, Object, Stone},
+    player::{
+        LocalPlayer, Player,
+        components::{EquippedTool, PlayerAim, PlayerAnimation, PlayerDirection},
+        messages::{Interact, UseTool},
+    },
+};
+use avian2d::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_ecs_tiled::prelude::TiledObject;
+use moonshine_kind::{Instance, SpawnInstance};
+use std::io::ErrorKind;
+
+/// Host sends a snapshot every N fixed ticks (64 Hz / 3 ≈ 21 Hz).
+const SNAPSHOT_INTERVAL: u32 = 3;
+/// Clients silent for longer than this (seconds) are dropped.
+const CLIENT_TIMEOUT: f64 = 5.0;
+/// Interval between Hello packets while waiting for a Welcome (seconds).
+const HELLO_INTERVAL: f64 = 0.5;
+/// Clients render this far in the past so there are two snapshots to interpolate between.
+const INTERPOLATION_DELAY: f64 = 0.1;
+/// Offset from the host's player where joining players appear.
+const JOIN_OFFSET: Vec3 = Vec3::new(16.0, 0.0, 0.0);
+
+/// Kind tags hashed into map entity ids.
+const KIND_NPC: u8 = 1;
+const KIND_OBJECT: u8 = 2;
+const KIND_DOOR: u8 = 3;
+const KIND_ITEM: u8 = 4;
+
+/// Run condition: true unless this instance is a network client.
+/// Gates the simulation (AI, movement, state machines) so clients only mirror the host.
+pub fn has_authority(mode: Res<NetMode>) -> bool {
+    *mode != NetMode::Client
+}
+
+/// Assigns deterministic NetIds to replicated map entities once their
+/// global transform is known.
+pub fn assign_map_net_ids(
+    mut commands: Commands,
+    entities: Query<
+        (Entity, &GlobalTransform, Has<Npc>, Has<Door>, Has<Item>),
+        (
+            Without<NetId>,
+            Or<(
+                With<Npc>,
+                With<Object>,
+                With<Door>,
+                (With<Item>, With<TiledObject>),
+            )>,
+        ),
+    >,
+) {
+    for (entity, transform, is_npc, is_door, is_item) in &entities {
+        let kind = if is_npc {
+            KIND_NPC
+        } else if is_door {
+            KIND_DOOR
+        } else if is_item {
+            KIND_ITEM
+        } else {
+            KIND_OBJECT
+        };
+        let id = NetId::from_spawn(kind, transform.translation().truncate());
+        commands.entity(entity).insert(id);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Host
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Gives the host's own player the first player id.
+pub fn assign_local_player_net_id(
+    mut commands: Commands,
+    players: Query<Instance<Player>, (With<LocalPlayer>, Without<NetId>)>,
+) {
+    for player in &players {
+        commands.entity(player.entity()).insert(NetId::player(0));
+    }
+}
+
+/// Gives items spawned at runtime (drops, produce) the next item id.
+/// Items placed in Tiled get map ids from `assign_map_net_ids` instead.
+pub fn assign_item_net_ids(
+    mut commands: Commands,
+    mut session: ResMut<HostSession>,
+    items: Query<Entity, (With<Item>, Without<NetId>, Without<TiledObject>)>,
+) {
+    for entity in &items {
+        commands
+            .entity(entity)
+            .insert(NetId::item(session.next_item));
+        session.next_item = session.next_item.wrapping_add(1);
+    }
+}
+
+/// Reads client packets: spawns players for new clients and stores their inputs.
+pub fn host_receive(
+    mut commands: Commands,
+    socket: Res<NetSocket>,
+    mut session: ResMut<HostSession>,
+    time: Res<Time>,
+    local_players: Query<&Transform, With<LocalPlayer>>,
+    mut inputs: Query<&mut RemoteInput>,
+) {
+    let now = time.elapsed_secs_f64();
+    let mut buf = [0u8; MAX_PACKET_SIZE];
+
+    loop {
+        let (len, addr) = match socket.0.recv_from(&mut buf) {
+            Ok(received) => received,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            Err(e) => {
+                warn!("Failed to receive packet: {e}");
+                break;
+            }
+        };
+
+        let Some(packet) = ClientPacket::decode(&buf[..len]) else {
+            continue;
+        };
+
+        match packet {
+            ClientPacket::Hello => {
+                let id = if let Some(client) = session.clients.get_mut(&addr) {
+                    // Welcome was lost, resend it
+                    client.last_seen = now;
+                    client.id
+                } else {
+                    session.next_player += 1;
+                    let id = NetId::player(session.next_player);
+                    let transform = local_players
+                        .iter()
+                        .next()
+                        .map(|t| Transform::from_translation(t.translation + JOIN_OFFSET))
+                        .unwrap_or_default();
+                    let player = commands
+                        .spawn_instance(Player)
+                        .insert((id, RemoteInput::default(), transform))
+                        .instance();
+                    session.clients.insert(
+                        addr,
+                        RemoteClient {
+                            player,
+                            id,
+                            last_seen: now,
+                        },
+                    );
+                    info!("Client {addr} joined as {id:?}");
+                    id
+                };
+
+                send_to(&socket, addr, &HostPacket::Welcome { player: id });
+            }
+            ClientPacket::Input(state) => {
+                let Some(client) = session.clients.get_mut(&addr) else {
+                    continue;
+                };
+                client.last_seen = now;
+
+                let Ok(mut input) = inputs.get_mut(client.player.entity()) else {
+                    continue;
+                };
+                // Drop reordered packets
+                if state.seq > input.state.seq {
+                    input.state = state;
+                }
+            }
+            ClientPacket::Bye => {
+                if let Some(client) = session.clients.remove(&addr) {
+                    info!("Client {addr} left");
+                    commands.entity(client.player.entity()).despawn();
+                }
+            }
+        }
+    }
+}
+
+/// Drives remote players from their latest input, like `detect_player_input` does locally.
+pub fn apply_remote_inputs(
+    mut players: Query<(
+        Instance<Player>,
+        &mut RemoteInput,
+        &mut PlayerDirection,
+        &mut EquippedTool,
+        &mut PlayerAim,
+    )>,
+    mut tool_writer: MessageWriter<UseTool>,
+    mut interact_writer: MessageWriter<Interact>,
+) {
+    for (player, mut input, mut direction, mut equipped, mut aim) in &mut players {
+        direction.0 = input.state.direction;
+        equipped.set_if_neq(input.state.equipped);
+        aim.set_if_neq(PlayerAim(input.state.aim));
+
+        if input.handled_actions != input.state.actions {
+            input.handled_actions = input.state.actions;
+            tool_writer.write(UseTool { player });
+        }
+
+        if input.handled_interactions != input.state.interactions {
+            input.handled_interactions = input.state.interactions;
+            interact_writer.write(Interact { player });
+        }
+    }
+}
+
+/// Despawns players of clients that stopped sending packets.
+pub fn drop_inactive_clients(
+    mut commands: Commands,
+    mut session: ResMut<HostSession>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs_f64();
+    session.clients.retain(|addr, client| {
+        let alive = now - client.last_seen <= CLIENT_TIMEOUT;
+        if !alive {
+            info!("Client {addr} timed out");
+            commands.entity(client.player.entity()).despawn();
+        }
+        alive
+    });
+}
+
+/// Broadcasts the authoritative world state to all clients.
+pub fn host_send_snapshots(
+    socket: Res<NetSocket>,
+    mut session: ResMut<HostSession>,
+    players: Query<(&NetId, &Transform, &PlayerAnimation), With<Player>>,
+    npcs: Query<
+        (
+            &NetId,
+            &Transform,
+            Has<Moving>,
+            Has<Fleeing>,
+            &Sprite,
+            &Visibility,
+            Option<(&Hunger, &Happiness, Option<&Milkable>)>,
+        ),
+        With<Npc>,
+    >,
+    objects: Query<(&NetId, &Health), With<Object>>,
+    doors: Query<(&NetId, &DoorState, Has<DoorRattle>)>,
+    items: Query<(&NetId, &Item, &Transform)>,
+    clock: Res<GameClock>,
+) {
+    session.tick = session.tick.wrapping_add(1);
+    if !session.tick.is_multiple_of(SNAPSHOT_INTERVAL) || session.clients.is_empty() {
+        return;
+    }
+
+    let snapshot = Snapshot {
+        tick: session.tick,
+        day: clock.day,
+        minutes: clock.minutes,
+        players: players
+            .iter()
+            .map(|(id, transform, animation)| PlayerSnapshot {
+                id: *id,
+                position: transform.translation.truncate(),
+                animation: *animation,
+            })
+            .collect(),
+        npcs: npcs
+            .iter()
+            .map(
+                |(id, transform, moving, fleeing, sprite, visibility, needs)| NpcSnapshot {
+                    id: *id,
+                    position: transform.translation.truncate(),
+                    moving,
+                    flip_x: sprite.flip_x,
+                    hidden: *visibility == Visibility::Hidden,
+                    fleeing,
+                    needs: needs.map(|(hunger, happiness, milkable)| NeedsSnapshot {
+                        hunger: hunger.value,
+                        happiness: happiness.value,
+                        petted_today: happiness.last_petted_day == Some(clock.day),
+                        milkable: milkable.map(|m| m.available),
+                    }),
+                },
+            )
+            .collect(),
+        objects: objects
+            .iter()
+            .map(|(id, health)| ObjectSnapshot {
+                id: *id,
+                health: health.current,
+            })
+            .collect(),
+        doors: doors
+            .iter()
+            .map(|(id, state, rattling)| DoorSnapshot {
+                id: *id,
+                state: *state,
+                rattling,
+            })
+            .collect(),
+        items: items
+            .iter()
+            .map(|(id, item, transform)| ItemSnapshot {
+                id: *id,
+                kind: item.kind,
+                position: transform.translation,
+            })
+            .collect(),
+    };
+
+    let bytes = HostPacket::Snapshot(snapshot).encode();
+    if bytes.len() > MAX_PACKET_SIZE {
+        warn!("Snapshot too large ({} bytes), not sent", bytes.len());
+        return;
+    }
+
+    for addr in session.clients.keys() {
+        if let Err(e) = socket.0.send_to(&bytes, addr) {
+            warn!("Failed to send snapshot to {addr}: {e}");
+        }
+    }
+}
+
+fn send_to(socket: &NetSocket, addr: std::net::SocketAddr, packet: &HostPacket) {
+    if let Err(e) = socket.0.send_to(&packet.encode(), addr) {
+        warn!("Failed to send packet to {addr}: {e}");
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Client
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Sends Hello until the host answers with a Welcome.
+pub fn send_hello(socket: Res<NetSocket>, mut session: ResMut<ClientSession>, time: Res<Time>) {
+    let now = time.elapsed_secs_f64();
+    if session.local_player.is_some() || now - session.last_hello < HELLO_INTERVAL {
+        return;
+    }
+    session.last_hello = now;
+    send(&socket, &ClientPacket::Hello);
+}
+
+/// Sends the local player's input to the host every frame.
+pub fn send_input(
+    socket: Res<NetSocket>,
+    mut session: ResMut<ClientSession>,
+    mut tool_requests: MessageReader<UseTool>,
+    mut interact_requests: MessageReader<Interact>,
+    players: Query<(&PlayerDirection, &EquippedTool, &PlayerAim), With<LocalPlayer>>,
+) {
+    for _ in tool_requests.read() {
+        session.actions = session.actions.wrapping_add(1);
+    }
+    for _ in interact_requests.read() {
+        session.interactions = session.interactions.wrapping_add(1);
+    }
+
+    let Ok((direction, equipped, aim)) = players.single() else {
+        return;
+    };
+
+    session.input_seq += 1;
+    let input = InputState {
+        seq: session.input_seq,
+        direction: direction.0,
+        equipped: *equipped,
+        actions: session.actions,
+        interactions: session.interactions,
+        aim: aim.0,
+    };
+    send(&socket, &ClientPacket::Input(input));
+}
+
+/// Tells the host we're leaving when the app exits.
+pub fn send_bye(socket: Res<NetSocket>, mut exit: MessageReader<AppExit>) {
+    if exit.read().next().is_some() {
+        send(&socket, &ClientPacket::Bye);
+    }
+}
+
+/// Reads host packets, keeping only the newest snapshot for this frame.
+pub fn client_receive(socket: Res<NetSocket>, mut session: ResMut<ClientSession>) {
+    let mut buf = [0u8; MAX_PACKET_SIZE];
+
+    loop {
+        let len = match socket.0.recv(&mut buf) {
+            Ok(len) => len,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            Err(e) => {
+                // Host not reachable (yet) - keep retrying via Hello
+                debug!("Failed to receive packet: {e}");
+                break;
+            }
+        };
+
+        match HostPacket::decode(&buf[..len]) {
+            Some(HostPacket::Welcome { player }) => {
+                if session.local_player.is_none() {
+                    info!("Joined session as {player:?}");
+                }
+                session.local_player = Some(player);
+            }
+            Some(HostPacket::Snapshot(snapshot)) => {
+                // Drop snapshots older than the newest one seen (UDP reordering)
+                if session.last_tick.is_some_and(|tick| snapshot.tick <= tick) {
+                    continue;
+                }
+                session.last_tick = Some(snapshot.tick);
+                session.pending = Some(snapshot);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Applies the pending snapshot: spawns/despawns players and items, queues positions
+/// for interpolation and mirrors animation, health and door states.
+pub fn apply_snapshot(
+    mut commands: Commands,
+    mut session: ResMut<ClientSession>,
+    time: Res<Time>,
+    ids: Query<(Entity, &NetId)>,
+    players: Query<(Entity, &NetId, Has<LocalPlayer>), With<Player>>,
+    objects: Query<(Entity, &NetId), With<Object>>,
+    items: Query<(Entity, &NetId), With<Item>>,
+    mut buffers: Query<&mut InterpolationBuffer>,
+    mut animations: Query<&mut PlayerAnimation>,
+    mut npcs: Query<(Has<Moving>, Has<Fleeing>, &mut Sprite, &mut Visibility), With<Npc>>,
+    mut needs: Query<(&mut Hunger, &mut Happiness, Option<&mut Milkable>)>,
+    mut healths: Query<&mut Health, With<Object>>,
+    mut door_states: Query<&mut DoorState>,
+    mut clock: ResMut<GameClock>,
+) {
+    let Some(snapshot) = session.pending.take() else {
+        return;
+    };
+
+    let now = time.elapsed_secs_f64();
+    let entities: HashMap<NetId, Entity> = ids.iter().map(|(e, id)| (*id, e)).collect();
+
+    // Players: update known ones, spawn newcomers, despawn those who left
+    for state in &snapshot.players {
+        let is_local = session.local_player == Some(state.id);
+
+        let Some(&entity) = entities.get(&state.id) else {
+            let mut buffer = InterpolationBuffer::default();
+            buffer.push(now, state.position);
+            let mut player = commands.spawn((
+                Player,
+                state.id,
+                state.animation,
+                buffer,
+                RigidBody::Kinematic,
+                Transform::from_translation(state.position.extend(10.0)),
+            ));
+            if is_local {
+                player.insert(LocalPlayer);
+            }
+            continue;
+        };
+
+        if let Ok(mut buffer) = buffers.get_mut(entity) {
+            buffer.push(now, state.position);
+        }
+        if let Ok(mut animation) = animations.get_mut(entity) {
+            animation.set_if_neq(state.animation);
+        }
+        // Welcome may arrive after our player was first replicated
+        if is_local && let Ok((_, _, false)) = players.get(entity) {
+            commands.entity(entity).insert(LocalPlayer);
+        }
+    }
+
+    for (entity, id, _) in &players {
+        if !snapshot.players.iter().any(|p| p.id == *id) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    // NPCs: position, walk/idle/flee state, facing, visibility and animal needs
+    for state in &snapshot.npcs {
+        let Some(&entity) = entities.get(&state.id) else {
+            continue;
+        };
+        let Ok((is_moving, is_fleeing, mut sprite, mut visibility)) = npcs.get_mut(entity) else {
+            continue;
+        };
+
+        if let Ok(mut buffer) = buffers.get_mut(entity) {
+            buffer.push(now, state.position);
+        } else {
+            // First snapshot for this NPC: the host drives it from now on
+            let mut buffer = InterpolationBuffer::default();
+            buffer.push(now, state.position);
+            commands
+                .entity(entity)
+                .insert((buffer, RigidBody::Kinematic));
+        }
+
+        if state.moving && !is_moving {
+            commands.entity(entity).insert(Moving);
+        } else if !state.moving && is_moving {
+            commands.entity(entity).remove::<Moving>();
+        }
+        if state.fleeing && !is_fleeing {
+            commands.entity(entity).insert(Fleeing);
+        } else if !state.fleeing && is_fleeing {
+            commands.entity(entity).remove::<Fleeing>();
+        }
+        sprite.flip_x = state.flip_x;
+        visibility.set_if_neq(if state.hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+
+        if let Some(state) = state.needs
+            && let Ok((mut hunger, mut happiness, milkable)) = needs.get_mut(entity)
+        {
+            hunger.value = state.hunger;
+            happiness.value = state.happiness;
+            happiness.last_petted_day = state.petted_today.then_some(snapshot.day);
+            if let (Some(mut milkable), Some(available)) = (milkable, state.milkable) {
+                milkable.available = available;
+            }
+        }
+    }
+
+    // Objects: health, and destruction when missing from the snapshot
+    for state in &snapshot.objects {
+        if let Some(&entity) = entities.get(&state.id)
+            && let Ok(mut health) = healths.get_mut(entity)
+        {
+            health.current = state.health;
+        }
+    }
+
+    for (entity, id) in &objects {
+        if !snapshot.objects.iter().any(|o| o.id == *id) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    // Doors: state changes drive the open/close animations and colliders
+    for state in &snapshot.doors {
+        if let Some(&entity) = entities.get(&state.id)
+            && let Ok(mut door_state) = door_states.get_mut(entity)
+        {
+            door_state.set_if_neq(state.state);
+            if state.rattling {
+                commands.entity(entity).insert_if_new(DoorRattle::new());
+            }
+        }
+    }
+
+    // Items: spawn drops and produce, despawn those collected on the host.
+    // Items placed in Tiled are already loaded with the map.
+    for state in &snapshot.items {
+        if !state.id.is_dynamic() || entities.contains_key(&state.id) {
+            continue;
+        }
+        let mut item = commands.spawn((
+            Item { kind: state.kind },
+            state.id,
+            Transform::from_translation(state.position),
+        ));
+        match state.kind {
+            ItemKind::Log => item.insert(Log),
+            ItemKind::Stone => item.insert(Stone),
+            ItemKind::Egg | ItemKind::LargeEgg => item.insert(Egg),
+            ItemKind::Milk | ItemKind::LargeMilk => item.insert(Milk),
+            ItemKind::Key => item.insert(Key),
+            ItemKind::Grass => item.insert(Grass),
+        };
+    }
+
+    for (entity, id) in &items {
+        if !snapshot.items.iter().any(|i| i.id == *id) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    clock.day = snapshot.day;
+    clock.minutes = snapshot.minutes;
+}
+
+/// Moves replicated entities along their buffered positions, slightly in the past.
+pub fn interpolate_replicated(
+    time: Res<Time>,
+    mut entities: Query<(&InterpolationBuffer, &mut Transform)>,
+) {
+    let render_time = time.elapsed_secs_f64() - INTERPOLATION_DELAY;
+
+    for (buffer, mut transform) in &mut entities {
+        if let Some(position) = buffer.sample(render_time) {
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+        }
+    }
+}
+
+fn send(socket: &NetSocket, packet: &ClientPacket) {
+    if let Err(e) = socket.0.send(&packet.encode()) {
+        debug!("Failed to send packet: {e}");
+    }
+}
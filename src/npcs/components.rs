@@ -1,12 +1,29 @@
+use crate::{
+    behaviour::Brain,
+    spatial::SpatialIndexed,
+    tiled::{OnSurface, TileOccupant},
+};
+use avian2d::prelude::*;
 use bevy::prelude::*;
+use serde::Deserialize;
 
 /// Marker for all NPC entities that use navigation regions.
+/// Their behaviour is picked by their `Brain`.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
+#[require(
+    Brain,
+    SpatialIndexed,
+    StuckDetection,
+    TileOccupant,
+    OnSurface,
+    TransformInterpolation
+)]
 pub struct Npc;
 
 /// Configuration for wandering behavior.
-#[derive(Component, Reflect)]
+/// NPCs with this component propose the Wander behaviour.
+#[derive(Component, Reflect, Deserialize, Clone)]
 #[reflect(Component)]
 pub struct WanderConfig {
     /// Minimum idle duration in seconds.
@@ -63,3 +80,48 @@ impl Default for IdleTimer {
         Self(Timer::from_seconds(5.0, TimerMode::Once))
     }
 }
+
+/// Configuration for detecting NPCs that stop getting closer to their waypoint
+/// (e.g. pushed against a wall by separation). Stuck NPCs repath once, then
+/// give up their destination.
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct StuckDetection {
+    /// Seconds without progress before the NPC counts as stuck.
+    pub timeout: f32,
+    /// Distance the NPC must close on its waypoint to count as progress.
+    pub min_progress: f32,
+}
+
+impl Default for StuckDetection {
+    fn default() -> Self {
+        Self {
+            timeout: 1.5,
+            min_progress: 2.0,
+        }
+    }
+}
+
+/// Runtime state tracking progress toward the current waypoint.
+/// Added while moving, removed when stopping.
+#[derive(Component)]
+pub struct WaypointProgress {
+    pub waypoint: Vec2,
+    /// Closest the NPC got to the waypoint.
+    pub closest: f32,
+    /// Seconds since the NPC last got closer.
+    pub stalled: f32,
+    /// Repaths since the NPC last reached a waypoint.
+    pub repaths: u32,
+}
+
+impl WaypointProgress {
+    pub fn new(waypoint: Vec2, distance: f32) -> Self {
+        Self {
+            waypoint,
+            closest: distance,
+            stalled: 0.0,
+            repaths: 0,
+        }
+    }
+}
@@ -1,15 +1,18 @@
 use super::{
-    components::{IdleTimer, Npc, WalkCycleProgress, WalkCycles, WanderConfig},
+    components::{IdleTimer, Npc, StuckDetection, WalkCycleProgress, WalkCycles, WanderConfig},
     systems::{
-        apply_npc_movement, flip_npc_sprite, idle_to_walk_transition, on_waypoint_arrival,
-        stop_npc_movement, tick_idle_timer,
+        advance_waypoints, apply_npc_movement, detect_stuck_npcs, flip_npc_sprite,
+        idle_to_walk_transition, on_waypoint_arrival, propose_wander, repath_on_navmesh_rebuild,
+        replan_across_disabled_links, stop_npc_movement, tick_idle_timer,
     },
 };
+use crate::behaviour::BehaviourSet;
+use crate::net::has_authority;
 use bevy::prelude::*;
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum NpcSystemSet {
-    /// AI state machine updates
+    /// AI behaviour updates (see `BehaviourSet`)
     StateMachine,
     /// Movement
     Movement,
@@ -24,27 +27,50 @@ impl Plugin for NpcsPlugin {
             .register_type::<IdleTimer>()
             .register_type::<WalkCycles>()
             .register_type::<WalkCycleProgress>()
+            .register_type::<StuckDetection>()
             // Configure system sets
             .configure_sets(
                 FixedUpdate,
                 (NpcSystemSet::StateMachine, NpcSystemSet::Movement).chain(),
             )
-            // State machine (FixedUpdate for determinism)
+            // Wander behaviour (FixedUpdate for determinism)
+            .add_systems(
+                FixedUpdate,
+                propose_wander
+                    .in_set(BehaviourSet::Propose)
+                    .run_if(has_authority),
+            )
             .add_systems(
                 FixedUpdate,
                 (
                     tick_idle_timer,
                     idle_to_walk_transition,
+                    advance_waypoints,
                     on_waypoint_arrival,
                 )
                     .chain()
-                    .in_set(NpcSystemSet::StateMachine),
+                    .in_set(BehaviourSet::Act)
+                    .run_if(has_authority),
+            )
+            // Recover NPCs that got stuck or whose path got blocked or closed
+            .add_systems(
+                FixedUpdate,
+                (
+                    replan_across_disabled_links,
+                    repath_on_navmesh_rebuild,
+                    detect_stuck_npcs,
+                )
+                    .chain()
+                    .in_set(NpcSystemSet::StateMachine)
+                    .after(BehaviourSet::Act)
+                    .run_if(has_authority),
             )
             // Movement (FixedUpdate, synced with physics)
             .add_systems(
                 FixedUpdate,
                 (apply_npc_movement, stop_npc_movement, flip_npc_sprite)
-                    .in_set(NpcSystemSet::Movement),
+                    .in_set(NpcSystemSet::Movement)
+                    .run_if(has_authority),
             );
     }
 }
@@ -1,8 +1,17 @@
-use super::components::{IdleTimer, Npc, WalkCycleProgress, WalkCycles, WanderConfig};
+use super::components::{
+    IdleTimer, Npc, StuckDetection, WalkCycleProgress, WalkCycles, WanderConfig, WaypointProgress,
+};
 use crate::{
+    behaviour::{Behaviour, BehaviourStatus, Brain, Priority},
     core::components::{Moving, Speed},
-    navigation::components::{InNavigationRegion, NavMeshRef, NavigationPath, NavigationRegion},
-    player::components::Player,
+    navigation::{
+        Pathfinder,
+        components::{
+            InNavigationRegion, NavMeshRef, NavigationLink, NavigationPath, NavigationRegion,
+        },
+    },
+    spatial::SpatialGrid,
+    tiled::OnSurface,
 };
 use avian2d::prelude::*;
 use bevy::prelude::*;
@@ -10,6 +19,13 @@ use moonshine_kind::Instance;
 use rand::Rng;
 use vleue_navigator::prelude::*;
 
+/// NPCs that can wander always offer to, as their lowest priority pastime.
+pub fn propose_wander(mut npcs: Query<&mut Brain, With<WanderConfig>>) {
+    for mut brain in &mut npcs {
+        brain.propose(Priority::WANDER, Behaviour::Wander);
+    }
+}
+
 /// Ticks idle timer for NPCs not moving.
 pub fn tick_idle_timer(time: Res<Time>, mut npcs: Query<&mut IdleTimer, Without<Moving>>) {
     for mut timer in &mut npcs {
@@ -17,13 +33,14 @@ pub fn tick_idle_timer(time: Res<Time>, mut npcs: Query<&mut IdleTimer, Without<
     }
 }
 
-/// Transitions from Idle to Walking when timer expires.
+/// Wander: transitions from Idle to Walking when timer expires.
 /// Computes a path to a random point in the navigation region.
 pub fn idle_to_walk_transition(
     mut commands: Commands,
     npcs: Query<
         (
             Instance<Npc>,
+            &Brain,
             &IdleTimer,
             &Transform,
             Option<&InNavigationRegion>,
@@ -38,8 +55,8 @@ pub fn idle_to_walk_transition(
 ) {
     let mut rng = rand::rng();
 
-    for (npc, timer, transform, in_region, walk_cycles, config) in &npcs {
-        if !timer.0.is_finished() {
+    for (npc, brain, timer, transform, in_region, walk_cycles, config) in &npcs {
+        if brain.current != Behaviour::Wander || !timer.0.is_finished() {
             continue;
         }
 
@@ -68,7 +85,9 @@ pub fn idle_to_walk_transition(
         };
 
         let current_pos = transform.translation.truncate();
-        let target_pos = region.random_point();
+        let Some(target_pos) = region.random_point() else {
+            continue;
+        };
 
         let Some(path) = navmesh.path(current_pos, target_pos) else {
             continue;
@@ -92,12 +111,28 @@ pub fn idle_to_walk_transition(
     }
 }
 
-/// Handles arrival at waypoint - advance path or go idle.
+/// Advances moving NPCs to their next waypoint once they reach the current one.
+pub fn advance_waypoints(mut npcs: Query<(&Transform, &mut NavigationPath), With<Moving>>) {
+    const ARRIVAL_THRESHOLD: f32 = 2.0;
+
+    for (transform, mut path) in &mut npcs {
+        let current_pos = transform.translation.truncate();
+
+        if let Some(waypoint) = path.current()
+            && current_pos.distance(waypoint) <= ARRIVAL_THRESHOLD
+        {
+            path.advance();
+        }
+    }
+}
+
+/// Wander: handles the end of a wander path - go idle or start a new walk cycle.
 pub fn on_waypoint_arrival(
     mut commands: Commands,
     mut npcs: Query<
         (
             Instance<Npc>,
+            &Brain,
             &Transform,
             &mut NavigationPath,
             &mut WalkCycleProgress,
@@ -111,15 +146,17 @@ pub fn on_waypoint_arrival(
     navmesh_query: Query<(&NavMeshStatus, &ManagedNavMesh)>,
     navmeshes: Res<Assets<NavMesh>>,
 ) {
-    const ARRIVAL_THRESHOLD: f32 = 2.0;
     let mut rng = rand::rng();
 
-    for (npc, transform, mut path, mut progress, mut timer, config, in_region) in &mut npcs {
+    for (npc, brain, transform, mut path, mut progress, mut timer, config, in_region) in &mut npcs {
+        if brain.current != Behaviour::Wander {
+            continue;
+        }
+
         let current_pos = transform.translation.truncate();
 
-        // Check if we reached current waypoint
-        let Some(waypoint) = path.current() else {
-            // Path complete - go idle or start new cycle
+        // Path complete - go idle or start new cycle
+        if path.current().is_none() {
             progress.current += 1;
 
             if progress.current >= progress.target {
@@ -144,18 +181,222 @@ pub fn on_waypoint_arrival(
                     continue;
                 };
 
-                let target_pos = region.random_point();
-                if let Some(new_path) = navmesh.path(current_pos, target_pos) {
+                if let Some(target_pos) = region.random_point()
+                    && let Some(new_path) = navmesh.path(current_pos, target_pos)
+                {
                     *path = NavigationPath::new(new_path.path, target_pos);
                 }
             }
+        }
+    }
+}
+
+/// Repaths allowed toward one waypoint before a stuck NPC gives up its destination.
+const MAX_REPATHS: u32 = 1;
+
+/// Tracks progress of moving NPCs toward their waypoint. NPCs that stall for
+/// `StuckDetection::timeout` repath to their destination; if they stay stuck
+/// they stop, so their behaviour picks a new destination (GoTo fails instead).
+pub fn detect_stuck_npcs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut npcs: Query<
+        (
+            Instance<Npc>,
+            &mut Brain,
+            &Transform,
+            &mut NavigationPath,
+            &StuckDetection,
+            Option<&mut WaypointProgress>,
+            Option<&InNavigationRegion>,
+        ),
+        With<Moving>,
+    >,
+    stopped: Query<Instance<Npc>, (With<WaypointProgress>, Without<Moving>)>,
+    pathfinder: Pathfinder,
+) {
+    for npc in &stopped {
+        commands.entity(npc.entity()).remove::<WaypointProgress>();
+    }
+
+    for (npc, mut brain, transform, mut path, config, progress, in_region) in &mut npcs {
+        let Some(waypoint) = path.current() else {
             continue;
         };
-
+        let current_pos = transform.translation.truncate();
         let distance = current_pos.distance(waypoint);
-        if distance <= ARRIVAL_THRESHOLD {
-            // Reached waypoint - advance to next
-            path.advance();
+
+        let Some(mut progress) = progress else {
+            commands
+                .entity(npc.entity())
+                .insert(WaypointProgress::new(waypoint, distance));
+            continue;
+        };
+
+        // Reached the previous waypoint or got a new path
+        if progress.waypoint != waypoint {
+            *progress = WaypointProgress::new(waypoint, distance);
+            continue;
+        }
+
+        if distance < progress.closest - config.min_progress {
+            progress.closest = distance;
+            progress.stalled = 0.0;
+            continue;
+        }
+        progress.stalled += time.delta_secs();
+        if progress.stalled < config.timeout {
+            continue;
+        }
+
+        // Stuck: look for another way to the same destination first
+        if progress.repaths < MAX_REPATHS
+            && let Some(new_path) = in_region
+                .and_then(|in_region| pathfinder.path(in_region.0, current_pos, path.destination))
+            && let Some(&first) = new_path.first()
+        {
+            let repaths = progress.repaths + 1;
+            *progress = WaypointProgress {
+                repaths,
+                ..WaypointProgress::new(first, current_pos.distance(first))
+            };
+            *path = NavigationPath::new(new_path, path.destination);
+            continue;
+        }
+
+        // Still stuck: give up this destination
+        debug!(
+            "NPC {:?} stuck on its way to {:?}, giving up",
+            npc.entity(),
+            path.destination
+        );
+        if matches!(brain.current, Behaviour::GoTo { .. }) {
+            brain.status = BehaviourStatus::Failed;
+        }
+        commands
+            .entity(npc.entity())
+            .remove::<(Moving, NavigationPath, WalkCycleProgress, WaypointProgress)>();
+    }
+}
+
+/// Spacing of the points checked along a path after a navmesh rebuild.
+const PATH_CHECK_STEP: f32 = 4.0;
+
+/// Whether a path from `start` through `waypoints` stays on the navmesh.
+/// The first step is skipped: NPCs pushed around may stand slightly off it.
+/// Only the waypoints in `region` are checked, the rest of the path lies
+/// beyond a link on other navmeshes.
+fn path_is_clear(
+    navmesh: &NavMesh,
+    region: &NavigationRegion,
+    start: Vec2,
+    waypoints: &[Vec2],
+) -> bool {
+    let mut from = start;
+    let mut travelled = 0.0;
+    for &to in waypoints
+        .iter()
+        .take_while(|&&waypoint| region.contains(waypoint))
+    {
+        let length = from.distance(to);
+        let mut along = (PATH_CHECK_STEP - travelled % PATH_CHECK_STEP) % PATH_CHECK_STEP;
+        while along <= length {
+            if travelled + along >= PATH_CHECK_STEP
+                && !navmesh.is_in_mesh(from.lerp(to, along / length.max(f32::EPSILON)))
+            {
+                return false;
+            }
+            along += PATH_CHECK_STEP;
+        }
+        travelled += length;
+        from = to;
+    }
+    true
+}
+
+/// Repaths moving NPCs whose path crosses an obstacle after their navmesh is
+/// rebuilt (e.g. `NavmeshUpdaterPlugin` carving out the player).
+pub fn repath_on_navmesh_rebuild(
+    rebuilt: Query<(Entity, &NavMeshStatus, &ManagedNavMesh), Changed<NavMeshStatus>>,
+    regions: Query<(&NavigationRegion, &NavMeshRef)>,
+    mut npcs: Query<(&Transform, &InNavigationRegion, &mut NavigationPath), With<Moving>>,
+    navmeshes: Res<Assets<NavMesh>>,
+    pathfinder: Pathfinder,
+) {
+    for (navmesh_entity, status, managed) in &rebuilt {
+        if *status != NavMeshStatus::Built {
+            continue;
+        }
+        let Some(navmesh) = navmeshes.get(&**managed) else {
+            continue;
+        };
+
+        for (transform, in_region, mut path) in &mut npcs {
+            let Ok((region, navmesh_ref)) = regions.get(in_region.0.entity()) else {
+                continue;
+            };
+            if navmesh_ref.0 != navmesh_entity {
+                continue;
+            }
+
+            let current_pos = transform.translation.truncate();
+            if path.current().is_none()
+                || path_is_clear(navmesh, region, current_pos, &path.waypoints)
+            {
+                continue;
+            }
+
+            // No way around: carry on, stuck detection handles it
+            if let Some(new_path) = pathfinder.path(in_region.0, current_pos, path.destination) {
+                *path = NavigationPath::new(new_path, path.destination);
+            }
+        }
+    }
+}
+
+/// Replans paths that cross a link when it gets disabled (closed door,
+/// raised gate). NPCs left without a way stop; GoTo fails.
+pub fn replan_across_disabled_links(
+    mut commands: Commands,
+    links: Query<&NavigationLink, Changed<NavigationLink>>,
+    mut npcs: Query<
+        (
+            Instance<Npc>,
+            &mut Brain,
+            &Transform,
+            &InNavigationRegion,
+            &mut NavigationPath,
+        ),
+        With<Moving>,
+    >,
+    pathfinder: Pathfinder,
+) {
+    for link in links.iter().filter(|link| !link.enabled) {
+        for (npc, mut brain, transform, in_region, mut path) in &mut npcs {
+            if !link.is_crossed_by(&path.waypoints) {
+                continue;
+            }
+
+            let current_pos = transform.translation.truncate();
+            if let Some(new_path) = pathfinder.path(in_region.0, current_pos, path.destination) {
+                *path = NavigationPath::new(new_path, path.destination);
+                continue;
+            }
+
+            debug!(
+                "NPC {:?} lost its way to {:?} to a closed link",
+                npc.entity(),
+                path.destination
+            );
+            if matches!(brain.current, Behaviour::GoTo { .. }) {
+                brain.status = BehaviourStatus::Failed;
+            }
+            commands.entity(npc.entity()).remove::<(
+                Moving,
+                NavigationPath,
+                WalkCycleProgress,
+                WaypointProgress,
+            )>();
         }
     }
 }
@@ -164,7 +405,8 @@ pub fn on_waypoint_arrival(
 const SEPARATION_RADIUS: f32 = 25.0;
 const SEPARATION_STRENGTH: f32 = 3.0;
 
-/// Applies velocity towards current waypoint with separation from nearby entities.
+/// Applies velocity towards current waypoint with separation from nearby
+/// NPCs and players (looked up in the spatial grid).
 pub fn apply_npc_movement(
     mut npcs: Query<
         (
@@ -173,20 +415,13 @@ pub fn apply_npc_movement(
             &Transform,
             &NavigationPath,
             &Speed,
+            &OnSurface,
         ),
         With<Moving>,
     >,
-    all_npcs: Query<(Instance<Npc>, &Transform)>,
-    player: Query<&Transform, With<Player>>,
+    grid: Res<SpatialGrid>,
 ) {
-    // Collect positions to avoid borrow issues
-    let npc_positions: Vec<(Instance<Npc>, Vec2)> = all_npcs
-        .iter()
-        .map(|(e, t)| (e, t.translation.truncate()))
-        .collect();
-    let player_pos = player.single().ok().map(|t| t.translation.truncate());
-
-    for (npc, mut velocity, transform, path, speed) in &mut npcs {
+    for (npc, mut velocity, transform, path, speed, surface) in &mut npcs {
         let Some(waypoint) = path.current() else {
             velocity.0 = Vec2::ZERO;
             continue;
@@ -199,22 +434,11 @@ pub fn apply_npc_movement(
 
         // Separation force from nearby entities
         let mut separation = Vec2::ZERO;
-
-        // Avoid other NPCs
-        for (other_npc, other_pos) in &npc_positions {
-            if *other_npc == npc {
+        for (other, other_pos) in grid.within(current_pos, SEPARATION_RADIUS) {
+            if other == npc.entity() {
                 continue;
             }
-            let diff = current_pos - *other_pos;
-            let dist = diff.length();
-            if dist > 0.0 && dist < SEPARATION_RADIUS {
-                separation += diff.normalize() * (1.0 - dist / SEPARATION_RADIUS);
-            }
-        }
-
-        // Avoid player
-        if let Some(p_pos) = player_pos {
-            let diff: Vec2 = current_pos - p_pos;
+            let diff = current_pos - other_pos;
             let dist = diff.length();
             if dist > 0.0 && dist < SEPARATION_RADIUS {
                 separation += diff.normalize() * (1.0 - dist / SEPARATION_RADIUS);
@@ -223,7 +447,7 @@ pub fn apply_npc_movement(
 
         // Blend path direction with separation
         let steering = path_dir + separation * SEPARATION_STRENGTH;
-        velocity.0 = steering.normalize_or_zero() * speed.0;
+        velocity.0 = steering.normalize_or_zero() * speed.0 * surface.speed_multiplier();
     }
 }
 
@@ -1,7 +1,10 @@
 use super::resources::{ObjectsAtlas, tiles};
 use crate::{
     core::components::Health,
+    inventory::ItemKind,
+    navigation::components::NavMeshObstacle,
     physics::GameLayer,
+    tiled::TileOccupant,
     tools::components::{Axe, ToolTarget},
 };
 use avian2d::prelude::*;
@@ -9,10 +12,12 @@ use bevy::{
     ecs::{lifecycle::HookContext, world::DeferredWorld},
     prelude::*,
 };
+use vleue_navigator::prelude::CachableObstacle;
 
 /// Marker for all object entities (Tree, Rock, Crop, etc.).
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
+#[require(TileOccupant = TileOccupant::BOTTOM_LEFT_TILE)]
 pub struct Object;
 
 /// Tree size variants with different properties.
@@ -75,10 +80,13 @@ impl Tree {
         let size = variant.collider_size();
         let offset = variant.collider_offset();
 
+        // Trunk carves the navmesh until the tree is chopped down
         world.commands().entity(entity).with_child((
             ToolTarget::<Axe>::new(),
             Collider::rectangle(size.x, size.y),
             Transform::from_translation(offset),
+            NavMeshObstacle,
+            CachableObstacle,
         ));
     }
 }
@@ -88,17 +96,27 @@ impl Tree {
 #[reflect(Component)]
 pub struct Collectable;
 
-/// A collectable log dropped by trees.
+/// An item lying in the world, added to the player's inventory when collected.
+/// Can be placed on Tiled objects (e.g. a key) or required by item components.
 #[derive(Component, Reflect, Default)]
-#[reflect(Component)]
+#[reflect(Component, Default)]
 #[require(
-    Name = "Log",
+    Name = "Item",
     Collectable,
+    TileOccupant,
     Sensor,
     Collider = Collider::rectangle(8.0, 6.0),
     CollisionLayers = CollisionLayers::new(GameLayer::Collectable, GameLayer::Player),
     CollisionEventsEnabled,
 )]
+pub struct Item {
+    pub kind: ItemKind,
+}
+
+/// A collectable log dropped by trees.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+#[require(Name = "Log", Item = Item { kind: ItemKind::Log })]
 #[component(on_add = Self::on_add)]
 pub struct Log;
 
@@ -116,6 +134,26 @@ impl Log {
     }
 }
 
+/// A collectable key dropped at runtime. Keys placed in Tiled use their tile
+/// as visual instead; there is no key art yet, so this is a plain gold bar.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+#[require(Name = "Key", Item = Item { kind: ItemKind::Key })]
+#[component(on_add = Self::on_add)]
+pub struct Key;
+
+impl Key {
+    const COLOR: Color = Color::srgb(0.95, 0.75, 0.2);
+    const SIZE: Vec2 = Vec2::new(8.0, 4.0);
+
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        world
+            .commands()
+            .entity(ctx.entity)
+            .insert(Sprite::from_color(Self::COLOR, Self::SIZE));
+    }
+}
+
 /// A rock that can be broken with an axe.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component, Default)]
@@ -129,6 +167,8 @@ impl Rock {
             ToolTarget::<Axe>::new(),
             Collider::rectangle(14.0, 10.0),
             Transform::from_translation(Vec3::new(8.0, 6.0, 0.0)),
+            NavMeshObstacle,
+            CachableObstacle,
         ));
     }
 }
@@ -136,14 +176,7 @@ impl Rock {
 /// A collectable stone dropped by rocks.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
-#[require(
-    Name = "Stone",
-    Collectable,
-    Sensor,
-    Collider = Collider::rectangle(8.0, 6.0),
-    CollisionLayers = CollisionLayers::new(GameLayer::Collectable, GameLayer::Player),
-    CollisionEventsEnabled,
-)]
+#[require(Name = "Stone", Item = Item { kind: ItemKind::Stone })]
 #[component(on_add = Self::on_add)]
 pub struct Stone;
 
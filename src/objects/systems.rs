@@ -1,7 +1,8 @@
-use super::components::{Collectable, Log, Rock, Stone, Tree};
+use super::components::{Collectable, Item, Log, Rock, Stone, Tree};
 use super::resources::ObjectsAtlas;
 use crate::core::components::{Damage, Health};
 use crate::core::messages::Hit;
+use crate::inventory::Inventory;
 use crate::player::Player;
 use crate::tools::components::Axe;
 use avian2d::prelude::*;
@@ -100,41 +101,37 @@ pub fn apply_axe_damage_rock(
     }
 }
 
-/// Collects items when player collides with them.
+/// Collects items into the player's inventory when player collides with them.
 pub fn collect_items(
     mut commands: Commands,
     mut collisions: MessageReader<CollisionStart>,
     player_colliders: Query<&ChildOf, With<Collider>>,
-    players: Query<Instance<Player>>,
-    collectables: Query<(Instance<Collectable>, &Name)>,
+    mut players: Query<&mut Inventory, With<Player>>,
+    collectables: Query<(Instance<Collectable>, &Item, &Name)>,
 ) {
     for evt in collisions.read() {
         // Check both orderings - one is player collider, other is collectable
-        let collectable_entity = if collectables.contains(evt.collider1) {
-            // Check if collider2 belongs to a player
-            let Ok(child_of) = player_colliders.get(evt.collider2) else {
-                continue;
-            };
-            if !players.contains(child_of.parent()) {
-                continue;
-            }
-            evt.collider1
+        let (collectable_entity, player_collider) = if collectables.contains(evt.collider1) {
+            (evt.collider1, evt.collider2)
         } else if collectables.contains(evt.collider2) {
-            let Ok(child_of) = player_colliders.get(evt.collider1) else {
-                continue;
-            };
-            if !players.contains(child_of.parent()) {
-                continue;
-            }
-            evt.collider2
+            (evt.collider2, evt.collider1)
         } else {
             continue;
         };
 
-        let Ok((collectable, name)) = collectables.get(collectable_entity) else {
+        // Check if the other collider belongs to a player
+        let Ok(child_of) = player_colliders.get(player_collider) else {
+            continue;
+        };
+        let Ok(mut inventory) = players.get_mut(child_of.parent()) else {
+            continue;
+        };
+
+        let Ok((collectable, item, name)) = collectables.get(collectable_entity) else {
             continue;
         };
 
+        inventory.add(item.kind, 1);
         info!("Collected: {}", name);
         commands.entity(collectable.entity()).despawn();
     }
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// Tiled object whose shape only marks an area (navigation regions, links,
+/// zones, building interiors): it gets no collider and doesn't carve the navmesh.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct NonSolidTiledObject;
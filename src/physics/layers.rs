@@ -8,7 +8,7 @@ pub enum GameLayer {
     Default,
     /// Player layer - collides with Default
     Player,
-    /// Interactable sensors - detect Player only
+    /// Interactable sensors - detect Player and Npc
     Interactable,
     /// Player tool sensors - active during tool actions (axe swing, etc.)
     Tool,
@@ -16,6 +16,6 @@ pub enum GameLayer {
     Object,
     /// Collectable items - can be picked up by Player
     Collectable,
-    /// NPC layer - chickens, etc. - collides with Default, Player, Npc
+    /// NPC layer - chickens, etc. - collides with Default, Player, Npc, seen by Interactable
     Npc,
 }
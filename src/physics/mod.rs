@@ -1,6 +1,8 @@
+mod components;
 mod layers;
 mod plugin;
 mod systems;
 
+pub use components::NonSolidTiledObject;
 pub use layers::GameLayer;
 pub use plugin::PhysicsPlugin;
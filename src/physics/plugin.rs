@@ -1,4 +1,7 @@
-use super::systems::{make_colliders_static, remove_navigation_region_colliders};
+use super::{
+    components::NonSolidTiledObject,
+    systems::{make_colliders_static, remove_non_solid_colliders},
+};
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_ecs_tiled::prelude::{TiledPhysicsAvianBackend, TiledPhysicsPlugin};
@@ -9,8 +12,9 @@ impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(PhysicsPlugins::default().with_length_unit(16.0))
             .add_plugins(TiledPhysicsPlugin::<TiledPhysicsAvianBackend>::default())
+            .register_type::<NonSolidTiledObject>()
             .insert_resource(Gravity(Vec2::ZERO))
             .add_observer(make_colliders_static)
-            .add_systems(Update, remove_navigation_region_colliders);
+            .add_systems(Update, remove_non_solid_colliders);
     }
 }
@@ -1,4 +1,4 @@
-use crate::navigation::components::NavigationRegion;
+use super::components::NonSolidTiledObject;
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_ecs_tiled::prelude::*;
@@ -13,13 +13,13 @@ pub fn make_colliders_static(
         .insert(RigidBody::Static);
 }
 
-/// Removes colliders from NavigationRegion entities and their children.
-pub fn remove_navigation_region_colliders(
+/// Removes colliders from non-solid Tiled objects and their children.
+pub fn remove_non_solid_colliders(
     mut commands: Commands,
-    nav_regions: Query<(Entity, Option<&Children>), With<NavigationRegion>>,
+    objects: Query<(Entity, Option<&Children>), With<NonSolidTiledObject>>,
     colliders: Query<Entity, With<Collider>>,
 ) {
-    for (entity, children) in &nav_regions {
+    for (entity, children) in &objects {
         // Remove from entity itself
         if colliders.contains(entity) {
             commands.entity(entity).remove::<(Collider, RigidBody)>();
@@ -1,14 +1,20 @@
 use super::resources::PlayerAtlas;
 use crate::{
     core::components::{Moving, Speed, YSort},
+    interactable::{Interactable, InteractionFocus},
+    inventory::Inventory,
     navigation::components::NavMeshObstacle,
     physics::GameLayer,
+    spatial::SpatialIndexed,
+    tiled::{OnSurface, TileOccupant},
+    tools::components::TargetTile,
 };
 use avian2d::prelude::*;
 use bevy::{
     ecs::{lifecycle::HookContext, world::DeferredWorld},
     prelude::*,
 };
+use moonshine_kind::Instance;
 
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
@@ -16,11 +22,21 @@ use bevy::{
     Name = "Player",
     Sprite,
     PlayerAnimation,
+    PlayerDirection,
+    PlayerAim,
+    PlayerMovementConfig,
     EquippedTool,
+    InteractionFocus,
+    TargetTile,
+    Inventory,
+    SpatialIndexed,
     RigidBody::Dynamic,
     LockedAxes::ROTATION_LOCKED,
+    TransformInterpolation,
     Speed = Speed(50.0),
     YSort = YSort { offset: -8.0 },
+    TileOccupant = TileOccupant { offset: Vec2::new(0.0, -8.0) },
+    OnSurface,
   )]
 #[component(on_add = Self::on_add)]
 pub struct Player;
@@ -66,7 +82,85 @@ impl Player {
     }
 }
 
-#[derive(Component, Reflect, Default, Clone, Copy, PartialEq, Eq)]
+/// Marker: player controlled by this instance's keyboard.
+/// Other players (e.g. co-op peers) are driven by the network instead.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct LocalPlayer;
+
+/// Movement direction requested by the player's input (keyboard or network).
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct PlayerDirection(pub Vec2);
+
+/// World position the player aims tools at (mouse cursor or network input).
+/// None when the cursor is outside the window.
+#[derive(Component, Reflect, Default, PartialEq)]
+#[reflect(Component)]
+pub struct PlayerAim(pub Option<Vec2>);
+
+/// How quickly the player's speed ramps up and down, and how far they get
+/// nudged around corners they brush.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct PlayerMovementConfig {
+    /// Speed gained per second while moving, in pixels/s².
+    pub acceleration: f32,
+    /// Speed lost per second once input stops, in pixels/s².
+    pub friction: f32,
+    /// Speed change per second when turning around, in pixels/s².
+    pub turn_acceleration: f32,
+    /// Furthest a corner blocking straight movement gets stepped around, in
+    /// pixels (0 disables it).
+    pub corner_correction: f32,
+}
+
+impl Default for PlayerMovementConfig {
+    fn default() -> Self {
+        Self {
+            acceleration: 600.0,
+            friction: 900.0,
+            turn_acceleration: 1200.0,
+            corner_correction: 3.0,
+        }
+    }
+}
+
+/// Walk to a clicked point along a navmesh path, then maybe act on what was
+/// clicked. Drives `PlayerDirection` until it arrives; movement keys cancel it.
+#[derive(Component)]
+pub struct AutoWalk {
+    /// Waypoints to follow (first = next target).
+    pub waypoints: Vec<Vec2>,
+    pub action: Option<AutoWalkAction>,
+    /// Closest the player got to the next waypoint.
+    pub closest: f32,
+    /// Seconds since the player last got closer.
+    pub stalled: f32,
+}
+
+impl AutoWalk {
+    pub fn new(waypoints: Vec<Vec2>, action: Option<AutoWalkAction>) -> Self {
+        Self {
+            waypoints,
+            action,
+            closest: f32::INFINITY,
+            stalled: 0.0,
+        }
+    }
+}
+
+/// What the player does at the end of an `AutoWalk`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AutoWalkAction {
+    /// Interact as soon as the interactable is in focus.
+    Interact(Instance<Interactable>),
+    /// Use the equipped tool, walking the last step in `facing` direction
+    /// so the player faces the target.
+    UseTool { facing: Vec2 },
+}
+
+#[derive(Component, Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
 #[reflect(Component)]
 pub enum PlayerAnimation {
     #[default]
@@ -156,7 +250,7 @@ impl Watering {
 }
 
 /// Currently equipped tool (editable as dropdown in inspector).
-#[derive(Component, Reflect, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Component, Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
 #[reflect(Component)]
 pub enum EquippedTool {
     #[default]
@@ -273,6 +367,32 @@ impl PlayerAnimation {
         }
     }
 
+    /// Unit vector of the direction the player is facing.
+    pub fn facing(self) -> Vec2 {
+        match self {
+            Self::IdleFront
+            | Self::WalkingFront
+            | Self::TilingFront
+            | Self::ChoppingFront
+            | Self::WateringFront => Vec2::NEG_Y,
+            Self::IdleBack
+            | Self::WalkingBack
+            | Self::TilingBack
+            | Self::ChoppingBack
+            | Self::WateringBack => Vec2::Y,
+            Self::IdleLeft
+            | Self::WalkingLeft
+            | Self::TilingLeft
+            | Self::ChoppingLeft
+            | Self::WateringLeft => Vec2::NEG_X,
+            Self::IdleRight
+            | Self::WalkingRight
+            | Self::TilingRight
+            | Self::ChoppingRight
+            | Self::WateringRight => Vec2::X,
+        }
+    }
+
     /// Returns tool offset based on animation direction.
     pub fn tool_offset(&self) -> Vec3 {
         match self {
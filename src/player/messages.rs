@@ -0,0 +1,17 @@
+use super::components::Player;
+use bevy::prelude::*;
+use moonshine_kind::Instance;
+
+/// Message fired when a player requests to use their equipped tool.
+#[derive(Message)]
+pub struct UseTool {
+    /// The player using the tool.
+    pub player: Instance<Player>,
+}
+
+/// Message fired when a player presses the interact key.
+#[derive(Message)]
+pub struct Interact {
+    /// The player interacting.
+    pub player: Instance<Player>,
+}
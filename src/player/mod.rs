@@ -1,7 +1,8 @@
 pub mod components;
+pub mod messages;
 mod plugin;
 mod resources;
 pub mod systems;
 
-pub use components::Player;
-pub use plugin::PlayerPlugin;
+pub use components::{LocalPlayer, Player};
+pub use plugin::{PlayerPlugin, PlayerSystemSet};
@@ -1,16 +1,22 @@
 use super::{
-    components::{Busy, Chopping, EquippedTool, Player, PlayerAnimation, Tiling, Watering},
-    resources::{PlayerActionsAtlas, PlayerAtlas, PlayerDirection},
+    components::{
+        Busy, Chopping, EquippedTool, LocalPlayer, Player, PlayerAim, PlayerAnimation,
+        PlayerDirection, PlayerMovementConfig, Tiling, Watering,
+    },
+    messages::{Interact, UseTool},
+    resources::{PlayerActionsAtlas, PlayerAtlas},
     systems::{
-        activate_tool_on_impact_frame, apply_player_movement, deactivate_tool_on_chopping_end,
-        detect_player_input, handle_tool_action, load_player_actions_atlas, load_player_atlas,
-        on_start_moving, on_start_tiling, on_start_watering, on_stop_moving,
-        position_tool_on_chopping, remove_chopping_on_animation_end,
-        remove_tiling_on_animation_end, remove_watering_on_animation_end,
-        spawn_player_at_spawn_point, sync_player_animation, update_animation_on_chopping,
-        update_moving_state, update_walking_direction,
+        activate_tool_on_impact_frame, apply_player_movement, correct_player_corners,
+        deactivate_tool_on_chopping_end, detect_aim_input, detect_click_to_move,
+        detect_interact_input, detect_player_input, detect_tool_input, follow_auto_walk,
+        handle_tool_action, load_player_actions_atlas, load_player_atlas, on_start_moving,
+        on_start_tiling, on_start_watering, on_stop_moving, position_tool_on_chopping,
+        remove_chopping_on_animation_end, remove_tiling_on_animation_end,
+        remove_watering_on_animation_end, spawn_player_at_spawn_point, sync_player_animation,
+        update_animation_on_chopping, update_moving_state, update_walking_direction,
     },
 };
+use crate::net::has_authority;
 use bevy::prelude::*;
 
 /// System sets for player operations with better parallelization
@@ -20,6 +26,10 @@ pub enum PlayerSystemSet {
     Movement,
     /// Animation updates (runs after movement)
     Animation,
+    /// Reading local keyboard and mouse input (runs in Update)
+    Input,
+    /// Starting tool actions from UseTool requests (runs in Update)
+    ToolActions,
 }
 
 pub struct PlayerPlugin;
@@ -29,6 +39,7 @@ impl Plugin for PlayerPlugin {
         app.register_type::<PlayerAtlas>()
             .register_type::<PlayerActionsAtlas>()
             .register_type::<Player>()
+            .register_type::<LocalPlayer>()
             .register_type::<Busy>()
             .register_type::<Chopping>()
             .register_type::<Tiling>()
@@ -36,26 +47,55 @@ impl Plugin for PlayerPlugin {
             .register_type::<EquippedTool>()
             .register_type::<PlayerAnimation>()
             .register_type::<PlayerDirection>()
-            .init_resource::<PlayerDirection>()
+            .register_type::<PlayerAim>()
+            .register_type::<PlayerMovementConfig>()
+            .add_message::<UseTool>()
+            .add_message::<Interact>()
             // Configure set ordering for FixedUpdate
             .configure_sets(
                 FixedUpdate,
                 (PlayerSystemSet::Movement, PlayerSystemSet::Animation).chain(),
             )
             .add_systems(Startup, (load_player_atlas, load_player_actions_atlas))
-            .add_systems(Update, spawn_player_at_spawn_point)
+            .add_systems(Update, spawn_player_at_spawn_point.run_if(has_authority))
             // Input runs in Update (every frame) for responsive input
-            .add_systems(Update, (detect_player_input, handle_tool_action))
+            .add_systems(
+                Update,
+                (
+                    (
+                        detect_player_input,
+                        detect_tool_input,
+                        detect_interact_input,
+                        detect_aim_input,
+                    )
+                        .in_set(PlayerSystemSet::Input),
+                    // Click-to-move steers after the keyboard, which cancels it
+                    (detect_click_to_move, follow_auto_walk)
+                        .chain()
+                        .after(PlayerSystemSet::Input),
+                ),
+            )
+            // Only the authoritative simulation starts tool actions
+            .add_systems(
+                Update,
+                handle_tool_action
+                    .after(detect_tool_input)
+                    .after(follow_auto_walk)
+                    .in_set(PlayerSystemSet::ToolActions)
+                    .run_if(has_authority),
+            )
             // Movement and animation run in FixedUpdate (synced with physics)
             .add_systems(
                 FixedUpdate,
                 (
                     (
                         apply_player_movement,
-                        update_moving_state.run_if(resource_changed::<PlayerDirection>),
+                        correct_player_corners,
+                        update_moving_state,
                     )
                         .chain()
-                        .in_set(PlayerSystemSet::Movement),
+                        .in_set(PlayerSystemSet::Movement)
+                        .run_if(has_authority),
                     (
                         (
                             on_start_moving,
@@ -69,7 +109,7 @@ impl Plugin for PlayerPlugin {
                             deactivate_tool_on_chopping_end,
                             remove_tiling_on_animation_end,
                             remove_watering_on_animation_end,
-                            update_walking_direction.run_if(resource_changed::<PlayerDirection>),
+                            update_walking_direction,
                         ),
                         sync_player_animation,
                     )
@@ -13,7 +13,3 @@ pub struct PlayerActionsAtlas {
     pub texture: Handle<Image>,
     pub layout: Handle<TextureAtlasLayout>,
 }
-
-#[derive(Resource, Default, Reflect)]
-#[reflect(Resource)]
-pub struct PlayerDirection(pub Vec2);
@@ -1,14 +1,22 @@
 use super::{
     components::{
-        Busy, Chopping, EquippedTool, Player, PlayerAnimation, PlayerAtlasKind, Tiling, Watering,
+        AutoWalk, AutoWalkAction, Busy, Chopping, EquippedTool, LocalPlayer, Player, PlayerAim,
+        PlayerAnimation, PlayerAtlasKind, PlayerDirection, PlayerMovementConfig, Tiling, Watering,
     },
+    messages::{Interact, UseTool},
     resources::{PlayerActionsAtlas, PlayerAtlas},
 };
 use crate::{
-    core::components::{Active, AnimationFinished, Moving, Speed, SpriteAnimation},
+    camera::GameCursor,
+    core::{
+        components::{Active, AnimationFinished, Moving, Speed, SpriteAnimation},
+        messages::TileHit,
+    },
+    interactable::{Interactable, InteractableSensor, InteractionFocus, InteractionMode},
+    navigation::Pathfinder,
     physics::GameLayer,
-    player::resources::PlayerDirection,
-    tools::components::ToolMarker,
+    tiled::OnSurface,
+    tools::components::{Axe, Hoe, TargetTile, ToolMarker, ToolTarget, WateringCan},
 };
 use avian2d::prelude::*;
 use bevy::prelude::*;
@@ -58,44 +66,336 @@ pub fn spawn_player_at_spawn_point(
         if name.0.as_str() == "PlayerSpawn" {
             let mut player_transform = *transform;
             player_transform.translation.z = 10.0;
-            commands.spawn((Player, player_transform));
+            commands.spawn((Player, LocalPlayer, player_transform));
         }
     }
 }
 
-/// Reads keyboard input and updates PlayerDirection resource.
+/// Reads keyboard input and updates the local player's PlayerDirection
 pub fn detect_player_input(
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut direction: ResMut<PlayerDirection>,
+    mut players: Query<&mut PlayerDirection, With<LocalPlayer>>,
 ) {
-    // Direction - overwrite each frame
-    direction.0 = Vec2::ZERO;
+    for mut direction in &mut players {
+        // Direction - overwrite each frame
+        direction.0 = Vec2::ZERO;
+
+        if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
+            direction.0.y += 1.0;
+        }
+
+        if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) {
+            direction.0.y -= 1.0;
+        }
+
+        if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
+            direction.0.x -= 1.0;
+        }
 
-    if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
-        direction.0.y += 1.0;
+        if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
+            direction.0.x += 1.0;
+        }
     }
+}
 
-    if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) {
-        direction.0.y -= 1.0;
+/// Points the local player's aim at the mouse cursor.
+pub fn detect_aim_input(cursor: GameCursor, mut players: Query<&mut PlayerAim, With<LocalPlayer>>) {
+    let position = cursor.world_position();
+    for mut aim in &mut players {
+        aim.set_if_neq(PlayerAim(position));
     }
+}
 
-    if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
-        direction.0.x -= 1.0;
+/// Auto-walk waypoints closer than this count as reached.
+const AUTO_WALK_REACHED: f32 = 2.0;
+/// Seconds an auto-walking player may go without getting closer to its next
+/// waypoint before it stops (or acts from there, on the last step).
+const AUTO_WALK_STALL_TIME: f32 = 0.5;
+/// Length of the straight step toward a tool target before using the tool.
+const TOOL_APPROACH_STEP: f32 = 6.0;
+
+/// Starts auto-walk for the local player on left click: to a tool target
+/// under the cursor (with the axe) or a manual interactable, acting on
+/// arrival, else to the clicked point.
+pub fn detect_click_to_move(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    cursor: GameCursor,
+    spatial_query: SpatialQuery,
+    players: Query<
+        (Instance<Player>, &Transform, &EquippedTool),
+        (With<LocalPlayer>, Without<Busy>),
+    >,
+    sensors: Query<(&ChildOf, &ColliderAabb), With<InteractableSensor>>,
+    interactables: Query<(Instance<Interactable>, &Interactable)>,
+    tool_targets: Query<&ColliderAabb, With<ToolTarget<Axe>>>,
+    pathfinder: Pathfinder,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(point) = cursor.world_position() else {
+        return;
+    };
+    let Ok((player, transform, tool)) = players.single() else {
+        return;
+    };
+    let position = transform.translation.truncate();
+    let hits = spatial_query.point_intersections(point, &SpatialQueryFilter::default());
+
+    let tool_target = hits
+        .iter()
+        .find_map(|hit| tool_targets.get(*hit).ok())
+        .filter(|_| *tool == EquippedTool::Axe);
+    if let Some(aabb) = tool_target {
+        if let Some(walk) = approach_tool_target(&pathfinder, position, aabb.center()) {
+            commands.entity(player.entity()).insert(walk);
+        }
+        return;
     }
 
-    if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
-        direction.0.x += 1.0;
+    let interactable = hits.iter().find_map(|hit| {
+        let (child_of, aabb) = sensors.get(*hit).ok()?;
+        let (instance, interactable) = interactables.get(child_of.parent()).ok()?;
+        (interactable.mode == InteractionMode::Manual).then_some((instance, aabb))
+    });
+    if let Some((interactable, aabb)) = interactable {
+        // Just inside its area, on the player's side
+        let margin = Vec2::splat(3.0).min(aabb.size() / 2.0);
+        let goal = position.clamp(aabb.min + margin, aabb.max - margin);
+        if let Some(waypoints) = pathfinder.player_path(position, goal) {
+            commands.entity(player.entity()).insert(AutoWalk::new(
+                waypoints,
+                Some(AutoWalkAction::Interact(interactable)),
+            ));
+        }
+        return;
+    }
+
+    if let Some(waypoints) = pathfinder.player_path(position, point) {
+        commands
+            .entity(player.entity())
+            .insert(AutoWalk::new(waypoints, None));
+    }
+}
+
+/// Path to where the tool lands on a target, on the player's side of it.
+/// The last step is walked straight at the target so the player faces it.
+fn approach_tool_target(pathfinder: &Pathfinder, position: Vec2, target: Vec2) -> Option<AutoWalk> {
+    let away = position - target;
+    let side = if away.x.abs() > away.y.abs() {
+        Vec2::new(away.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, away.y.signum())
+    };
+    let facing = -side;
+    let tool_offset = walking_animation_for(facing)
+        .to_chopping()
+        .tool_offset()
+        .truncate();
+    let stand = target - tool_offset;
+
+    // Start the last step further out if the spot is too close to obstacles
+    (1..=4).find_map(|steps| {
+        let approach = stand + side * TOOL_APPROACH_STEP * steps as f32;
+        let mut waypoints = pathfinder.player_path(position, approach)?;
+        waypoints.push(stand);
+        Some(AutoWalk::new(
+            waypoints,
+            Some(AutoWalkAction::UseTool { facing }),
+        ))
+    })
+}
+
+/// Steers the local player along its auto-walk path, performing the walk's
+/// action on arrival. Movement keys cancel it.
+pub fn follow_auto_walk(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut players: Query<
+        (
+            Instance<Player>,
+            &Transform,
+            &InteractionFocus,
+            &mut PlayerDirection,
+            &mut AutoWalk,
+        ),
+        With<LocalPlayer>,
+    >,
+    mut tool_writer: MessageWriter<UseTool>,
+    mut interact_writer: MessageWriter<Interact>,
+) {
+    for (player, transform, focus, mut direction, mut walk) in &mut players {
+        // Runs after `detect_player_input`, so a direction means movement keys
+        if direction.0 != Vec2::ZERO {
+            commands.entity(player.entity()).remove::<AutoWalk>();
+            continue;
+        }
+
+        // Interactables act as soon as they are in reach
+        if let Some(AutoWalkAction::Interact(target)) = walk.action
+            && focus.0 == Some(target)
+        {
+            interact_writer.write(Interact { player });
+            direction.0 = Vec2::ZERO;
+            commands.entity(player.entity()).remove::<AutoWalk>();
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let Some(waypoint) = walk.waypoints.first().copied() else {
+            direction.0 = Vec2::ZERO;
+            commands.entity(player.entity()).remove::<AutoWalk>();
+            continue;
+        };
+        let last_step = walk.waypoints.len() == 1;
+        let facing = match walk.action {
+            Some(AutoWalkAction::UseTool { facing }) if last_step => Some(facing),
+            _ => None,
+        };
+
+        let distance = position.distance(waypoint);
+        let reached = match facing {
+            // Up to (or past) the spot in front of the target
+            Some(facing) => (waypoint - position).dot(facing) <= 1.0,
+            None => distance <= AUTO_WALK_REACHED,
+        };
+        if reached {
+            walk.waypoints.remove(0);
+            walk.closest = f32::INFINITY;
+            walk.stalled = 0.0;
+        } else if distance < walk.closest - 0.5 {
+            walk.closest = distance;
+            walk.stalled = 0.0;
+        } else {
+            walk.stalled += time.delta_secs();
+        }
+
+        let blocked = walk.stalled >= AUTO_WALK_STALL_TIME;
+        if walk.waypoints.is_empty() || blocked {
+            // A blocked last step still gets the tool close enough to try
+            if matches!(walk.action, Some(AutoWalkAction::UseTool { .. }))
+                && (last_step || walk.waypoints.is_empty())
+            {
+                tool_writer.write(UseTool { player });
+            }
+            direction.0 = Vec2::ZERO;
+            commands.entity(player.entity()).remove::<AutoWalk>();
+            continue;
+        }
+
+        direction.0 = match facing {
+            Some(facing) => facing,
+            None => (walk.waypoints[0] - position).normalize_or_zero(),
+        };
     }
 }
 
-/// Applies velocity to player based on input direction and speed.
+/// Full speed of a player (changed by the surface).
+fn top_speed(speed: &Speed, surface: &OnSurface) -> f32 {
+    speed.0 * surface.speed_multiplier()
+}
+
+/// Accelerates the player toward their input direction at full speed, or
+/// slows them down with friction once input stops.
 /// Skipped when player is Busy (using tool).
 pub fn apply_player_movement(
-    direction: Res<PlayerDirection>,
-    mut player: Query<(&mut LinearVelocity, &Speed), (With<Player>, Without<Busy>)>,
+    time: Res<Time>,
+    mut player: Query<
+        (
+            &mut LinearVelocity,
+            &Speed,
+            &PlayerDirection,
+            &OnSurface,
+            &PlayerMovementConfig,
+        ),
+        (With<Player>, Without<Busy>),
+    >,
 ) {
-    for (mut velocity, speed) in &mut player {
-        velocity.0 = direction.0.normalize_or_zero() * speed.0;
+    for (mut velocity, speed, direction, surface, config) in &mut player {
+        let target = direction.0.normalize_or_zero() * top_speed(speed, surface);
+        let rate = if target == Vec2::ZERO {
+            config.friction
+        } else if velocity.0.dot(target) < 0.0 {
+            config.turn_acceleration
+        } else {
+            config.acceleration
+        };
+        velocity.0 = velocity.0.move_towards(target, rate * time.delta_secs());
+    }
+}
+
+/// Small gap left when checking for walls ahead, in pixels.
+const CORNER_SKIN: f32 = 0.5;
+
+/// Steps players around corners blocking straight movement: if the way
+/// ahead is clear a few pixels to either side, they're nudged sideways.
+pub fn correct_player_corners(
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    mut players: Query<
+        (
+            &mut LinearVelocity,
+            &Transform,
+            &Children,
+            &Speed,
+            &PlayerDirection,
+            &OnSurface,
+            &PlayerMovementConfig,
+        ),
+        (With<Player>, Without<Busy>),
+    >,
+    bodies: Query<(&Collider, &Transform), Without<Sensor>>,
+    sensors: Query<(), With<Sensor>>,
+) {
+    let filter = SpatialQueryFilter::from_mask(GameLayer::Default);
+    let not_sensor = |entity: Entity| !sensors.contains(entity);
+
+    for (mut velocity, transform, children, speed, direction, surface, config) in &mut players {
+        // Only straight movement, diagonal movement already slides along walls
+        if config.corner_correction <= 0.0 || direction.0.x != 0.0 && direction.0.y != 0.0 {
+            continue;
+        }
+        let Ok(ahead) = Dir2::new(direction.0) else {
+            continue;
+        };
+        let Some((collider, body_transform)) =
+            children.iter().find_map(|child| bodies.get(child).ok())
+        else {
+            continue;
+        };
+
+        let speed = top_speed(speed, surface);
+        let origin = transform.translation.truncate() + body_transform.translation.truncate();
+        let config_ahead =
+            ShapeCastConfig::from_max_distance(speed * time.delta_secs() + CORNER_SKIN);
+        let blocked = |offset: Vec2| {
+            spatial_query
+                .cast_shape_predicate(
+                    collider,
+                    origin + offset,
+                    0.0,
+                    ahead,
+                    &config_ahead,
+                    &filter,
+                    &not_sensor,
+                )
+                .is_some()
+        };
+        if !blocked(Vec2::ZERO) {
+            continue;
+        }
+
+        // Smallest clear offset, trying both sides at each distance
+        let side = ahead.perp();
+        let steps = (1..=config.corner_correction as u32).map(|step| step as f32);
+        let nudge = steps
+            .flat_map(|step| [side * step, -side * step])
+            .find(|&offset| !blocked(offset));
+        if let Some(offset) = nudge {
+            let sideways = offset.normalize() * (offset.length() / time.delta_secs()).min(speed);
+            velocity.0 = ahead * speed + sideways;
+        }
     }
 }
 
@@ -103,10 +403,12 @@ pub fn apply_player_movement(
 /// Skipped when player is Busy (using tool).
 pub fn update_moving_state(
     mut commands: Commands,
-    direction: Res<PlayerDirection>,
-    player: Query<(Instance<Player>, Has<Moving>), Without<Busy>>,
+    player: Query<
+        (Instance<Player>, &PlayerDirection, Has<Moving>),
+        (Without<Busy>, Changed<PlayerDirection>),
+    >,
 ) {
-    for (player, is_moving) in &player {
+    for (player, direction, is_moving) in &player {
         let has_direction = direction.0 != Vec2::ZERO;
 
         if has_direction && !is_moving {
@@ -118,11 +420,8 @@ pub fn update_moving_state(
 }
 
 /// Switches to walking animation when player starts moving.
-pub fn on_start_moving(
-    direction: Res<PlayerDirection>,
-    mut player: Query<&mut PlayerAnimation, Added<Moving>>,
-) {
-    for mut anim in &mut player {
+pub fn on_start_moving(mut player: Query<(&PlayerDirection, &mut PlayerAnimation), Added<Moving>>) {
+    for (direction, mut anim) in &mut player {
         *anim = walking_animation_for(direction.0);
     }
 }
@@ -141,10 +440,12 @@ pub fn on_stop_moving(
 
 /// Updates walking direction while moving.
 pub fn update_walking_direction(
-    direction: Res<PlayerDirection>,
-    mut player: Query<&mut PlayerAnimation, With<Moving>>,
+    mut player: Query<
+        (&PlayerDirection, &mut PlayerAnimation),
+        (With<Moving>, Changed<PlayerDirection>),
+    >,
 ) {
-    for mut anim in &mut player {
+    for (direction, mut anim) in &mut player {
         let new_anim = walking_animation_for(direction.0);
         if *anim != new_anim {
             *anim = new_anim;
@@ -164,31 +465,76 @@ fn walking_animation_for(dir: Vec2) -> PlayerAnimation {
     }
 }
 
-/// Triggers tool action when Space is pressed.
-pub fn handle_tool_action(
-    mut commands: Commands,
+/// Requests a tool action for the local player when Space is pressed.
+pub fn detect_tool_input(
     keyboard: Res<ButtonInput<KeyCode>>,
-    player: Query<(Instance<Player>, &EquippedTool, Has<Busy>)>,
+    players: Query<Instance<Player>, With<LocalPlayer>>,
+    mut writer: MessageWriter<UseTool>,
 ) {
     if !keyboard.just_pressed(KeyCode::Space) {
         return;
     }
 
-    for (player, tool, is_busy) in &player {
+    for player in &players {
+        writer.write(UseTool { player });
+    }
+}
+
+/// Requests an interaction for the local player when E is pressed.
+pub fn detect_interact_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    players: Query<Instance<Player>, With<LocalPlayer>>,
+    mut writer: MessageWriter<Interact>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyE) {
+        return;
+    }
+
+    for player in &players {
+        writer.write(Interact { player });
+    }
+}
+
+/// Starts the equipped tool's action for each UseTool request.
+/// The hoe and watering can act on the player's target tile, and only when
+/// it is valid for them.
+pub fn handle_tool_action(
+    mut commands: Commands,
+    mut requests: MessageReader<UseTool>,
+    players: Query<(&EquippedTool, &TargetTile, Has<Busy>), With<Player>>,
+    mut hoe_hits: MessageWriter<TileHit<Hoe>>,
+    mut watering_hits: MessageWriter<TileHit<WateringCan>>,
+) {
+    for request in requests.read() {
+        let player = request.player;
+        let Ok((tool, target, is_busy)) = players.get(player.entity()) else {
+            continue;
+        };
+
         // Only trigger if not already performing an action
         if is_busy {
             continue;
         }
 
+        let target_tile = target.tile.filter(|_| target.valid);
+
         match tool {
             EquippedTool::Axe => {
                 commands.entity(player.entity()).insert(Chopping);
             }
             EquippedTool::Hoe => {
+                let Some(tile) = target_tile else {
+                    continue;
+                };
                 commands.entity(player.entity()).insert(Tiling);
+                hoe_hits.write(TileHit::new(tile, player));
             }
             EquippedTool::WateringCan => {
+                let Some(tile) = target_tile else {
+                    continue;
+                };
                 commands.entity(player.entity()).insert(Watering);
+                watering_hits.write(TileHit::new(tile, player));
             }
             EquippedTool::None => {}
         }
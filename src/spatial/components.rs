@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+/// Marker: entity is tracked in the `SpatialGrid`.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct SpatialIndexed;
@@ -0,0 +1,8 @@
+pub mod components;
+mod plugin;
+pub mod resources;
+mod systems;
+
+pub use components::SpatialIndexed;
+pub use plugin::SpatialPlugin;
+pub use resources::SpatialGrid;
@@ -0,0 +1,21 @@
+use super::{components::SpatialIndexed, resources::SpatialGrid, systems::update_spatial_grid};
+use crate::npcs::NpcSystemSet;
+use bevy::prelude::*;
+
+/// Runs before the NPC simulation so its proximity queries see this tick's positions.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpatialSystemSet;
+
+pub struct SpatialPlugin;
+
+impl Plugin for SpatialPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpatialIndexed>()
+            .init_resource::<SpatialGrid>()
+            .configure_sets(
+                FixedUpdate,
+                SpatialSystemSet.before(NpcSystemSet::StateMachine),
+            )
+            .add_systems(FixedUpdate, update_spatial_grid.in_set(SpatialSystemSet));
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// Positions of `SpatialIndexed` entities bucketed in square cells, so
+/// proximity queries only look at the few cells around a point instead of
+/// every entity. Rebuilt once per fixed tick (see `SpatialSystemSet`).
+#[derive(Resource)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Entity, Vec2)>>,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self {
+            cell_size: Self::CELL_SIZE,
+            cells: HashMap::default(),
+        }
+    }
+}
+
+impl SpatialGrid {
+    /// Cell side, in pixels. About the radius of typical queries.
+    pub const CELL_SIZE: f32 = 32.0;
+
+    fn cell(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    /// Removes every entity, keeping the cells used last time allocated.
+    pub fn clear(&mut self) {
+        self.cells.retain(|_, entities| !entities.is_empty());
+        for entities in self.cells.values_mut() {
+            entities.clear();
+        }
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec2) {
+        let cell = self.cell(position);
+        self.cells.entry(cell).or_default().push((entity, position));
+    }
+
+    /// Entities within `radius` of `center`, with their position.
+    pub fn within(&self, center: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let min = self.cell(center - Vec2::splat(radius));
+        let max = self.cell(center + Vec2::splat(radius));
+        let radius_squared = radius * radius;
+
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(move |(_, position)| position.distance_squared(center) <= radius_squared)
+    }
+}
@@ -0,0 +1,15 @@
+use super::{components::SpatialIndexed, resources::SpatialGrid};
+use avian2d::prelude::RigidBodyDisabled;
+use bevy::prelude::*;
+
+/// Rebuilds the grid from the current positions of indexed entities.
+/// Disabled bodies (e.g. animals sheltered in their home) are left out.
+pub fn update_spatial_grid(
+    mut grid: ResMut<SpatialGrid>,
+    entities: Query<(Entity, &Transform), (With<SpatialIndexed>, Without<RigidBodyDisabled>)>,
+) {
+    grid.clear();
+    for (entity, transform) in &entities {
+        grid.insert(entity, transform.translation.truncate());
+    }
+}
@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use bevy_ecs_tiled::prelude::tiled;
+
+/// Entity tracked in the `TileGrid` on the tile under it.
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component, Default)]
+pub struct TileOccupant {
+    /// Point deciding the tile, relative to the entity (e.g. its feet).
+    pub offset: Vec2,
+}
+
+impl TileOccupant {
+    /// For sprites anchored at their bottom-left corner, like Tiled tile
+    /// objects: the center of their bottom-left 16x16 tile.
+    pub const BOTTOM_LEFT_TILE: Self = Self {
+        offset: Vec2::splat(8.0),
+    };
+}
+
+/// Ground type of a tile, from the `surface` property of the tile or its
+/// tileset in Tiled. Changes how fast things walk over it, and which
+/// footstep sounds and particles to play.
+#[derive(Reflect, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Surface {
+    Grass,
+    Path,
+    TilledSoil,
+    WoodFloor,
+    ShallowWater,
+}
+
+impl Surface {
+    /// Name of the Tiled string property holding the surface.
+    pub const PROPERTY: &str = "surface";
+
+    /// Parses a `surface` property. Warns on unknown names, so only call it
+    /// when loading a map.
+    pub fn from_property(value: &tiled::PropertyValue) -> Option<Self> {
+        let tiled::PropertyValue::StringValue(name) = value else {
+            return None;
+        };
+        match name.as_str() {
+            "grass" => Some(Self::Grass),
+            "path" => Some(Self::Path),
+            "tilled_soil" => Some(Self::TilledSoil),
+            "wood_floor" => Some(Self::WoodFloor),
+            "shallow_water" => Some(Self::ShallowWater),
+            _ => {
+                warn!("Unknown surface '{name}'");
+                None
+            }
+        }
+    }
+
+    /// Multiplier applied to walking speed on this surface.
+    pub fn speed_multiplier(self) -> f32 {
+        match self {
+            Self::Grass | Self::WoodFloor => 1.0,
+            Self::Path => 1.15,
+            Self::TilledSoil => 0.85,
+            Self::ShallowWater => 0.6,
+        }
+    }
+}
+
+/// Surface under an entity's `TileOccupant` point (its feet), if its tile
+/// declares one.
+#[derive(Component, Reflect, Default, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+#[require(TileOccupant)]
+pub struct OnSurface(pub Option<Surface>);
+
+impl OnSurface {
+    pub fn speed_multiplier(&self) -> f32 {
+        self.0.map_or(1.0, Surface::speed_multiplier)
+    }
+}
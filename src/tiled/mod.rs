@@ -1,4 +1,8 @@
+pub mod components;
 mod plugin;
+pub mod resources;
 mod systems;
 
+pub use components::{OnSurface, TileOccupant};
 pub use plugin::TiledPlugin;
+pub use resources::{TileCoord, TileGrid};
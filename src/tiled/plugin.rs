@@ -1,4 +1,11 @@
-use super::systems::{load_map, spawn_map_from_object};
+use super::{
+    components::{OnSurface, Surface, TileOccupant},
+    resources::TileGrid,
+    systems::{
+        add_map_to_tile_grid, load_map, spawn_map_from_object, update_surfaces,
+        update_tile_occupants,
+    },
+};
 use bevy::prelude::*;
 
 pub struct TiledPlugin;
@@ -6,7 +13,18 @@ pub struct TiledPlugin;
 impl Plugin for TiledPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(bevy_ecs_tiled::prelude::TiledPlugin::default())
+            .register_type::<TileOccupant>()
+            .register_type::<Surface>()
+            .register_type::<OnSurface>()
+            .init_resource::<TileGrid>()
             .add_systems(Startup, load_map)
-            .add_observer(spawn_map_from_object);
+            .add_systems(
+                PostUpdate,
+                (update_tile_occupants, update_surfaces)
+                    .chain()
+                    .after(TransformSystems::Propagate),
+            )
+            .add_observer(spawn_map_from_object)
+            .add_observer(add_map_to_tile_grid);
     }
 }
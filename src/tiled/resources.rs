@@ -0,0 +1,245 @@
+use super::components::Surface;
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_ecs_tiled::prelude::*;
+
+/// A tile of one of the loaded maps. `pos` counts from the map's
+/// bottom-left tile, like `bevy_ecs_tilemap`'s `TilePos`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TileCoord {
+    pub map: Entity,
+    pub pos: TilePos,
+}
+
+/// Tiles of every loaded Tiled map, so farming, placement and targeting can
+/// work in tiles instead of world positions: converts between world and tile
+/// coordinates, reports the tile layers and custom tile properties at a tile,
+/// and tracks which `TileOccupant`s are on each tile.
+#[derive(Resource, Default)]
+pub struct TileGrid {
+    maps: HashMap<Entity, MapTiles>,
+    occupants: HashMap<TileCoord, Vec<Entity>>,
+    occupied: HashMap<Entity, TileCoord>,
+    /// Set when a map is added, so occupants spawned before it get placed.
+    pub(super) rescan: bool,
+}
+
+/// Layout and tile layers of one map.
+pub struct MapTiles {
+    /// Map translation in the world.
+    origin: Vec2,
+    size: TilemapSize,
+    grid_size: TilemapGridSize,
+    tile_size: TilemapTileSize,
+    map_type: TilemapType,
+    anchor: TilemapAnchor,
+    /// Tile layers, bottom to top.
+    layers: Vec<MapTileLayer>,
+    /// Custom properties of tileset tiles, over those of their tileset, by
+    /// tileset index and tile id. Only tiles with properties are kept.
+    properties: HashMap<(usize, u32), TileProperties>,
+}
+
+/// Custom properties of a tileset tile, and the ones the game understands,
+/// parsed once when the map is added.
+pub struct TileProperties {
+    pub custom: tiled::Properties,
+    /// Parsed `surface` property (None if missing or unknown).
+    pub surface: Option<Surface>,
+}
+
+/// A tile layer of a map, and which tileset tile is on each of its tiles.
+pub struct MapTileLayer {
+    pub name: String,
+    /// The `TiledLayer` entity.
+    pub entity: Entity,
+    tiles: HashMap<TilePos, (usize, u32)>,
+}
+
+impl MapTiles {
+    pub fn new(
+        map: &TiledMapAsset,
+        storage: &TiledMapStorage,
+        anchor: TilemapAnchor,
+        origin: Vec2,
+    ) -> Self {
+        let mut tiles = Self {
+            origin,
+            size: map.tilemap_size,
+            grid_size: grid_size_from_map(&map.map),
+            tile_size: map.largest_tile_size,
+            map_type: tilemap_type_from_map(&map.map),
+            anchor,
+            layers: Vec::new(),
+            properties: HashMap::default(),
+        };
+        for layer in map.map.layers() {
+            tiles.add_layer(map, storage, layer);
+        }
+        tiles
+    }
+
+    /// Adds a tile layer, or the tile layers of a group.
+    fn add_layer(&mut self, map: &TiledMapAsset, storage: &TiledMapStorage, layer: tiled::Layer) {
+        let name = layer.name.clone();
+        match layer.layer_type() {
+            tiled::LayerType::Tiles(tile_layer) => {
+                let Some(entity) = storage.get_layer_entity(layer.id()) else {
+                    return;
+                };
+                let mut tiles = HashMap::default();
+                map.for_each_tile(&tile_layer, |layer_tile, _, tile_pos, _| {
+                    let key = (layer_tile.tileset_index(), layer_tile.id());
+                    if !self.properties.contains_key(&key) {
+                        let mut properties = layer_tile.get_tileset().properties.clone();
+                        if let Some(tile) = layer_tile.get_tile() {
+                            properties.extend(tile.properties.clone());
+                        }
+                        if !properties.is_empty() {
+                            let surface = properties
+                                .get(Surface::PROPERTY)
+                                .and_then(Surface::from_property);
+                            self.properties.insert(
+                                key,
+                                TileProperties {
+                                    custom: properties,
+                                    surface,
+                                },
+                            );
+                        }
+                    }
+                    tiles.insert(tile_pos, key);
+                });
+                self.layers.push(MapTileLayer {
+                    name,
+                    entity,
+                    tiles,
+                });
+            }
+            tiled::LayerType::Group(group) => {
+                for layer in group.layers() {
+                    self.add_layer(map, storage, layer);
+                }
+            }
+            tiled::LayerType::Objects(_) | tiled::LayerType::Image(_) => {}
+        }
+    }
+
+    fn tile_at(&self, position: Vec2) -> Option<TilePos> {
+        TilePos::from_world_pos(
+            &(position - self.origin),
+            &self.size,
+            &self.grid_size,
+            &self.tile_size,
+            &self.map_type,
+            &self.anchor,
+        )
+    }
+
+    fn tile_center(&self, pos: TilePos) -> Vec2 {
+        self.origin
+            + pos.center_in_world(
+                &self.size,
+                &self.grid_size,
+                &self.tile_size,
+                &self.map_type,
+                &self.anchor,
+            )
+    }
+}
+
+impl TileGrid {
+    pub fn insert_map(&mut self, map: Entity, tiles: MapTiles) {
+        self.maps.insert(map, tiles);
+        self.rescan = true;
+    }
+
+    /// The tile at a world position. Where maps overlap (e.g. a building
+    /// placed on the farm), the smallest map wins.
+    pub fn tile_at(&self, position: Vec2) -> Option<TileCoord> {
+        self.maps
+            .iter()
+            .filter_map(|(&map, tiles)| {
+                Some((
+                    tiles,
+                    TileCoord {
+                        map,
+                        pos: tiles.tile_at(position)?,
+                    },
+                ))
+            })
+            .min_by_key(|(tiles, _)| tiles.size.count())
+            .map(|(_, tile)| tile)
+    }
+
+    /// World position of the center of a tile.
+    pub fn tile_center(&self, tile: TileCoord) -> Option<Vec2> {
+        Some(self.maps.get(&tile.map)?.tile_center(tile.pos))
+    }
+
+    /// World space area covered by a tile.
+    pub fn tile_rect(&self, tile: TileCoord) -> Option<Rect> {
+        let tiles = self.maps.get(&tile.map)?;
+        let size = Vec2::new(tiles.grid_size.x, tiles.grid_size.y);
+        Some(Rect::from_center_size(tiles.tile_center(tile.pos), size))
+    }
+
+    /// Tile layers with a tile on `tile`, bottom to top.
+    pub fn layers_at(&self, tile: TileCoord) -> impl Iterator<Item = &MapTileLayer> {
+        self.maps
+            .get(&tile.map)
+            .into_iter()
+            .flat_map(|tiles| &tiles.layers)
+            .filter(move |layer| layer.tiles.contains_key(&tile.pos))
+    }
+
+    /// Custom properties of the tiles on `tile`, with their layer name, top
+    /// to bottom. Tiles without properties are skipped.
+    pub fn properties_at(&self, tile: TileCoord) -> impl Iterator<Item = (&str, &TileProperties)> {
+        self.maps.get(&tile.map).into_iter().flat_map(move |tiles| {
+            tiles.layers.iter().rev().filter_map(move |layer| {
+                let key = layer.tiles.get(&tile.pos)?;
+                Some((layer.name.as_str(), tiles.properties.get(key)?))
+            })
+        })
+    }
+
+    /// Surface of the topmost tile on `tile` declaring one.
+    pub fn surface_at(&self, tile: TileCoord) -> Option<Surface> {
+        self.properties_at(tile)
+            .find(|(_, properties)| properties.custom.contains_key(Surface::PROPERTY))
+            .and_then(|(_, properties)| properties.surface)
+    }
+
+    /// Entities on a tile.
+    pub fn occupants(&self, tile: TileCoord) -> &[Entity] {
+        self.occupants.get(&tile).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn is_occupied(&self, tile: TileCoord) -> bool {
+        !self.occupants(tile).is_empty()
+    }
+
+    /// The tile an occupant is on.
+    pub fn occupant_tile(&self, entity: Entity) -> Option<TileCoord> {
+        self.occupied.get(&entity).copied()
+    }
+
+    /// Moves an occupant to a tile, or off the grid.
+    pub(super) fn set_occupant(&mut self, entity: Entity, tile: Option<TileCoord>) {
+        if self.occupied.get(&entity).copied() == tile {
+            return;
+        }
+        if let Some(previous) = self.occupied.remove(&entity)
+            && let Some(entities) = self.occupants.get_mut(&previous)
+        {
+            entities.retain(|&occupant| occupant != entity);
+            if entities.is_empty() {
+                self.occupants.remove(&previous);
+            }
+        }
+        if let Some(tile) = tile {
+            self.occupied.insert(entity, tile);
+            self.occupants.entry(tile).or_default().push(entity);
+        }
+    }
+}
@@ -1,3 +1,7 @@
+use super::{
+    components::{OnSurface, TileOccupant},
+    resources::{MapTiles, TileGrid},
+};
 use bevy::prelude::*;
 use bevy_ecs_tiled::prelude::*;
 
@@ -16,7 +20,6 @@ pub fn spawn_map_from_object(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     map_assets: Res<Assets<TiledMapAsset>>,
-    transforms: Query<&Transform>,
 ) {
     let event = trigger.event();
 
@@ -34,17 +37,60 @@ pub fn spawn_map_from_object(
         return;
     };
 
-    // Get the object's transform
-    let Ok(object_transform) = transforms.get(event.origin) else {
+    // Spawn the referenced tilemap as a child of the object, so it follows the
+    // object's position and Y-sort (see `YSortAnchor`)
+    // Use BottomLeft anchor to match Tiled's tile object anchor point
+    let map_handle = asset_server.load(format!("tiled/maps/{}", path));
+    commands
+        .entity(event.origin)
+        .with_child((TiledMap(map_handle), TilemapAnchor::BottomLeft));
+}
+
+/// Adds each map's tiles to the `TileGrid` once it's spawned.
+pub fn add_map_to_tile_grid(
+    trigger: On<TiledEvent<MapCreated>>,
+    mut grid: ResMut<TileGrid>,
+    maps: Query<(&TiledMapStorage, &TilemapAnchor, &GlobalTransform)>,
+    map_assets: Res<Assets<TiledMapAsset>>,
+) {
+    let event = trigger.event();
+    let Ok((storage, anchor, transform)) = maps.get(event.origin) else {
+        return;
+    };
+    let Some(map) = event.get_map_asset(&map_assets) else {
         return;
     };
 
-    // Spawn the referenced tilemap at the object's position
-    // Use BottomLeft anchor to match Tiled's tile object anchor point
-    let map_handle = asset_server.load(format!("tiled/maps/{}", path));
-    commands.spawn((
-        TiledMap(map_handle),
-        TilemapAnchor::BottomLeft,
-        *object_transform,
-    ));
+    let origin = transform.translation().truncate();
+    grid.insert_map(event.origin, MapTiles::new(map, storage, *anchor, origin));
+}
+
+/// Keeps `TileGrid` occupants on the tile under them.
+pub fn update_tile_occupants(
+    mut grid: ResMut<TileGrid>,
+    occupants: Query<(Entity, Ref<GlobalTransform>, Ref<TileOccupant>)>,
+    mut removed: RemovedComponents<TileOccupant>,
+) {
+    for entity in removed.read() {
+        grid.set_occupant(entity, None);
+    }
+
+    let rescan = std::mem::take(&mut grid.rescan);
+    for (entity, transform, occupant) in &occupants {
+        if !rescan && !transform.is_changed() && !occupant.is_changed() {
+            continue;
+        }
+        let tile = grid.tile_at(transform.translation().truncate() + occupant.offset);
+        grid.set_occupant(entity, tile);
+    }
+}
+
+/// Updates the surface under each `OnSurface` entity.
+pub fn update_surfaces(grid: Res<TileGrid>, mut entities: Query<(Entity, &mut OnSurface)>) {
+    for (entity, mut on_surface) in &mut entities {
+        let surface = grid
+            .occupant_tile(entity)
+            .and_then(|tile| grid.surface_at(tile));
+        on_surface.set_if_neq(OnSurface(surface));
+    }
 }
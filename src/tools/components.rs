@@ -1,4 +1,4 @@
-use crate::{core::components::Damage, physics::GameLayer};
+use crate::{core::components::Damage, physics::GameLayer, tiled::TileCoord};
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use std::marker::PhantomData;
@@ -76,3 +76,26 @@ impl<T: Tool> Default for ToolTarget<T> {
 fn tool_target_collision_layers() -> CollisionLayers {
     CollisionLayers::new(GameLayer::Object, GameLayer::Tool)
 }
+
+/// Tile a player's tools act on: the tile in front of their feet, or for the
+/// local player the tile under the mouse cursor when it's within reach.
+#[derive(Component, Default, PartialEq)]
+pub struct TargetTile {
+    pub tile: Option<TileCoord>,
+    /// Whether the equipped tool can be used on the tile.
+    pub valid: bool,
+}
+
+impl TargetTile {
+    /// How far from the player's feet the cursor can pick a tile, in pixels.
+    pub const REACH: f32 = 24.0;
+}
+
+/// Tile layer whose tiles can be tilled with the hoe (e.g. grass).
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct TillableLayer;
+
+/// Gizmos of the local player's target tile cursor.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct TileCursorGizmos;
@@ -1,7 +1,11 @@
 use super::{
-    components::{Axe, Hoe, ToolMarker, WateringCan},
-    systems::{detect_tool_hits, sync_tool_on_equip_change},
+    components::{Axe, Hoe, TileCursorGizmos, TillableLayer, ToolMarker, WateringCan},
+    systems::{
+        detect_tool_hits, draw_tile_cursor, log_tile_hits, sync_tool_on_equip_change,
+        update_target_tiles,
+    },
 };
+use crate::player::PlayerSystemSet;
 use bevy::prelude::*;
 
 pub struct ToolsPlugin;
@@ -12,14 +16,36 @@ impl Plugin for ToolsPlugin {
             .register_type::<Axe>()
             .register_type::<Hoe>()
             .register_type::<WateringCan>()
+            .register_type::<TillableLayer>()
+            .insert_gizmo_config(
+                TileCursorGizmos,
+                GizmoConfig {
+                    line: GizmoLineConfig {
+                        width: 1.0,
+                        ..default()
+                    },
+                    ..default()
+                },
+            )
             .add_systems(Update, sync_tool_on_equip_change)
+            // The hoe and watering can act on the target tile instead of what they touch
             .add_systems(
                 Update,
                 (
                     detect_tool_hits::<Axe>,
-                    detect_tool_hits::<Hoe>,
-                    detect_tool_hits::<WateringCan>,
+                    log_tile_hits::<Hoe>,
+                    log_tile_hits::<WateringCan>,
                 ),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_target_tiles
+                        .after(PlayerSystemSet::Input)
+                        .before(PlayerSystemSet::ToolActions),
+                    draw_tile_cursor,
+                )
+                    .chain(),
             );
     }
 }
@@ -1,11 +1,15 @@
-use super::components::{Axe, Hoe, Tool, ToolMarker, ToolTarget, WateringCan};
+use super::components::{
+    Axe, Hoe, TargetTile, TileCursorGizmos, TillableLayer, Tool, ToolMarker, ToolTarget,
+    WateringCan,
+};
 use crate::core::components::Active;
-use crate::core::messages::Hit;
+use crate::core::messages::{Hit, TileHit};
 use crate::objects::components::Object;
-use crate::player::Player;
-use crate::player::components::EquippedTool;
+use crate::player::components::{EquippedTool, PlayerAim, PlayerAnimation};
+use crate::player::{LocalPlayer, Player};
+use crate::tiled::{TileCoord, TileGrid, TileOccupant};
 use avian2d::prelude::*;
-use bevy::prelude::*;
+use bevy::{color::palettes, prelude::*};
 use moonshine_kind::Instance;
 
 /// Syncs tool child entity when EquippedTool changes.
@@ -73,3 +77,102 @@ pub fn detect_tool_hits<T: Tool>(
         writer.write(Hit::new(target, tool));
     }
 }
+
+/// Logs tile tool use. Nothing tills or waters tiles yet, this is where it goes.
+pub fn log_tile_hits<T: Tool>(mut hits: MessageReader<TileHit<T>>) {
+    for hit in hits.read() {
+        debug!(
+            "{} used on tile {:?} by {:?}",
+            ShortName::of::<T>(),
+            hit.tile,
+            hit.player.entity()
+        );
+    }
+}
+
+/// Picks each player's target tile and checks whether their equipped tool
+/// can be used on it.
+pub fn update_target_tiles(
+    grid: Res<TileGrid>,
+    mut players: Query<(
+        &Transform,
+        &TileOccupant,
+        &PlayerAnimation,
+        &EquippedTool,
+        &PlayerAim,
+        &mut TargetTile,
+    )>,
+    tillable_layers: Query<(), With<TillableLayer>>,
+    objects: Query<(), With<Object>>,
+) {
+    for (transform, occupant, animation, tool, aim, mut target) in &mut players {
+        let feet = transform.translation.truncate() + occupant.offset;
+
+        // An aimed tile in reach wins over the facing one
+        let hovered = aim
+            .0
+            .and_then(|position| grid.tile_at(position))
+            .filter(|&tile| {
+                grid.tile_center(tile)
+                    .is_some_and(|center| center.distance(feet) <= TargetTile::REACH)
+            });
+        let facing = || {
+            let step = grid.tile_rect(grid.tile_at(feet)?)?.size();
+            grid.tile_at(feet + animation.facing() * step)
+        };
+        let tile = hovered.or_else(facing);
+
+        target.set_if_neq(TargetTile {
+            tile,
+            valid: tile
+                .is_some_and(|tile| can_use_on(*tool, tile, &grid, &tillable_layers, &objects)),
+        });
+    }
+}
+
+/// Whether a tool can be used on a tile: the hoe on free tillable ground,
+/// the watering can on tillable ground, the axe on objects.
+fn can_use_on(
+    tool: EquippedTool,
+    tile: TileCoord,
+    grid: &TileGrid,
+    tillable_layers: &Query<(), With<TillableLayer>>,
+    objects: &Query<(), With<Object>>,
+) -> bool {
+    let tillable = || {
+        grid.layers_at(tile)
+            .any(|layer| tillable_layers.contains(layer.entity))
+    };
+    match tool {
+        EquippedTool::None => false,
+        EquippedTool::Axe => grid
+            .occupants(tile)
+            .iter()
+            .any(|&entity| objects.contains(entity)),
+        EquippedTool::Hoe => tillable() && !grid.is_occupied(tile),
+        EquippedTool::WateringCan => tillable(),
+    }
+}
+
+/// Outlines the local player's target tile while a tool is equipped, red
+/// when the tool can't be used there.
+pub fn draw_tile_cursor(
+    mut gizmos: Gizmos<TileCursorGizmos>,
+    grid: Res<TileGrid>,
+    players: Query<(&TargetTile, &EquippedTool), With<LocalPlayer>>,
+) {
+    for (target, tool) in &players {
+        if *tool == EquippedTool::None {
+            continue;
+        }
+        let Some(rect) = target.tile.and_then(|tile| grid.tile_rect(tile)) else {
+            continue;
+        };
+        let color = if target.valid {
+            palettes::tailwind::GRAY_50
+        } else {
+            palettes::tailwind::RED_500
+        };
+        gizmos.rect_2d(rect.center(), rect.size(), color);
+    }
+}